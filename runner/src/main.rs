@@ -0,0 +1,97 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use tokio::time::interval;
+use vehicle_dynamics::{Car, Event, Gear, HandBrake, Identity, RandomDriver};
+
+#[tokio::main]
+async fn main() {
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt::init();
+
+    let mut rng = rand::thread_rng();
+    let mut avg_speed = 0.0;
+
+    let mut car = Car::new(rng.gen_range(0.0..1.0));
+    car.set_identity(Identity::random(&mut rng));
+    // Default config has no start inhibitors
+    _ = car.turn_key(true);
+    car.set_handbrake_position(HandBrake::Disengaged);
+    car.set_clutch_position(1.0);
+    car.shift_gear(Gear::First);
+    car.set_clutch_position(0.5);
+    car.set_accelerator_position(0.5);
+    car.set_clutch_position(0.0);
+
+    let mut interval = interval(Duration::from_secs(1));
+    let mut refuelling = None;
+    let mut driver = RandomDriver::default();
+
+    loop {
+        car.update();
+        avg_speed = (avg_speed + car.speed()) * 0.5;
+        let events: Vec<_> = car.drain_events().collect();
+        display(&car, avg_speed, &events);
+        interval.tick().await;
+
+        // Stop for refuelling, slowly get into the gas station
+        if car.fuel_level() < 0.25 && car.speed() != 0.0 {
+            let position = rng.gen_range(0.3..0.7);
+            car.set_clutch_position(position);
+            car.set_brake_position(position);
+            continue;
+        }
+        // Start refuelling
+        if car.fuel_level() < 0.25 && car.speed() == 0.0 && refuelling.is_none() {
+            car.set_handbrake_position(HandBrake::Full);
+            refuelling = Some(
+                // Time during which car is stationary at the refuelling point: between 7.5-17.5 minutes
+                Instant::now() + Duration::from_secs_f32(300.0 + 60.0 * rng.gen_range(2.5..12.5)),
+            );
+        }
+
+        if let Some(till) = refuelling {
+            if till < Instant::now() {
+                refuelling.take();
+                car.set_handbrake_position(HandBrake::Disengaged);
+                continue;
+            }
+            car.refuel(0.001);
+            continue;
+        }
+
+        driver.drive(&mut car, &mut rng);
+    }
+}
+
+// Events are traced as the car raises them
+#[cfg(feature = "tracing")]
+fn display(car: &Car, avg_speed: f64, _events: &[Event]) {
+    tracing::info!(
+        vin = car.identity().map(|identity| identity.vin.as_str()),
+        speed = car.speed(),
+        fuel = car.fuel_level() * car.config().tank_capacity,
+        gear = ?car.gear(),
+        rpm = car.rpm(),
+        accelerator = car.accelerator_position(),
+        brake = car.brake_position(),
+        clutch = car.clutch_position(),
+        hand_brake = ?car.hand_brake(),
+        drive_mode = ?car.drive_mode(),
+        power_state = ?car.power_state(),
+        distance = car.odometer(),
+        avg_speed,
+    );
+}
+
+#[cfg(not(feature = "tracing"))]
+fn display(car: &Car, avg_speed: f64, events: &[Event]) {
+    use std::io::{self, IsTerminal};
+
+    println!("\t----");
+    println!("{}", car.dashboard().color(io::stdout().is_terminal()));
+    println!("Average speed: {avg_speed:.1} kmph");
+    for event in events {
+        println!("Event: {event:?}");
+    }
+}
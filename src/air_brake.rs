@@ -0,0 +1,69 @@
+use serde::Serialize;
+
+/// Compressed air service brakes of heavy vehicles, pressures in bar
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AirBrakeConfig {
+    /// governor starts the compressor below this
+    pub cut_in: f64,
+    /// and stops it above this
+    pub cut_out: f64,
+    /// low pressure warning threshold
+    pub warning: f64,
+    /// pressure gained per second while the compressor runs
+    pub compressor_rate: f64,
+    /// pressure used by going from released to fully applied
+    pub consumption: f64,
+}
+
+impl Default for AirBrakeConfig {
+    fn default() -> Self {
+        Self { cut_in: 7.0, cut_out: 8.5, warning: 5.5, compressor_rate: 0.2, consumption: 0.6 }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AirBrake {
+    pub pressure: f64,
+    compressor_on: bool,
+    last_brake_position: f64,
+}
+
+impl AirBrake {
+    pub fn new(pressure: f64) -> Self {
+        Self { pressure, ..Default::default() }
+    }
+
+    /// Share of the requested braking the brakes can deliver at the current pressure
+    pub fn effectiveness(&self, config: &AirBrakeConfig) -> f64 {
+        (self.pressure / config.cut_in).clamp(0.0, 1.0)
+    }
+
+    /// Returns true if pressure just dropped below the warning threshold
+    pub fn update(
+        &mut self,
+        config: &AirBrakeConfig,
+        brake_position: f64,
+        engine_running: bool,
+        time_step: f64,
+    ) -> bool {
+        let was_above_warning = self.pressure >= config.warning;
+
+        // Each further push of the pedal lets air into the brake chambers
+        let applied = (brake_position - self.last_brake_position).max(0.0);
+        self.pressure = (self.pressure - applied * config.consumption).max(0.0);
+        self.last_brake_position = brake_position;
+
+        // Governor, with hysteresis between cut in and cut out
+        if self.pressure < config.cut_in {
+            self.compressor_on = true;
+        } else if self.pressure >= config.cut_out {
+            self.compressor_on = false;
+        }
+        if self.compressor_on && engine_running {
+            self.pressure =
+                (self.pressure + config.compressor_rate * time_step).min(config.cut_out);
+        }
+
+        was_above_warning && self.pressure < config.warning
+    }
+}
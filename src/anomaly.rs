@@ -0,0 +1,128 @@
+use std::io;
+
+use rand::Rng;
+
+use crate::{Telemetry, TelemetrySink, KM_PER_MILE};
+
+/// Per record probabilities of data quality problems, these only affect what's reported, not the car
+#[derive(Debug, Clone, Default)]
+pub struct Anomalies {
+    /// record is sent twice
+    pub duplicate: f64,
+    /// record is held back and sent after the next one
+    pub out_of_order: f64,
+    /// speed and rpm stop changing for `stuck_for` records, never when that's 0
+    pub stuck: f64,
+    pub stuck_for: u32,
+    /// speed reported in mph and fuel level in percent
+    pub unit_flip: f64,
+}
+
+/// Ground truth of what was injected
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InjectedAnomalies {
+    pub duplicated: usize,
+    pub out_of_order: usize,
+    pub stuck: usize,
+    pub unit_flipped: usize,
+}
+
+/// Wraps a sink and corrupts the stream going into it, for validating data-cleaning pipelines
+pub struct AnomalySink<S, R> {
+    inner: S,
+    anomalies: Anomalies,
+    rng: R,
+    held: Option<Telemetry>,
+    /// record the values are stuck at and for how many more records
+    stuck: Option<(Telemetry, u32)>,
+    injected: InjectedAnomalies,
+}
+
+impl<S: TelemetrySink, R: Rng> AnomalySink<S, R> {
+    /// Probabilities are clamped to 0.0..=1.0, NaN counts as 0.0
+    pub fn new(inner: S, anomalies: Anomalies, rng: R) -> Self {
+        let probability = |p: f64| if p.is_nan() { 0.0 } else { p.clamp(0.0, 1.0) };
+        let anomalies = Anomalies {
+            duplicate: probability(anomalies.duplicate),
+            out_of_order: probability(anomalies.out_of_order),
+            stuck: if anomalies.stuck_for == 0 { 0.0 } else { probability(anomalies.stuck) },
+            unit_flip: probability(anomalies.unit_flip),
+            ..anomalies
+        };
+
+        Self { inner, anomalies, rng, held: None, stuck: None, injected: Default::default() }
+    }
+
+    pub fn injected(&self) -> InjectedAnomalies {
+        self.injected
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn corrupt(&mut self, telemetry: &Telemetry) -> Telemetry {
+        let mut telemetry = telemetry.clone();
+
+        if self.stuck.is_none() && self.rng.gen_bool(self.anomalies.stuck) {
+            self.stuck = Some((telemetry.clone(), self.anomalies.stuck_for));
+        }
+        if let Some((stuck_at, remaining)) = &mut self.stuck {
+            telemetry.speed = stuck_at.speed;
+            telemetry.rpm = stuck_at.rpm;
+            self.injected.stuck += 1;
+            trace_injection("stuck");
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 {
+                self.stuck = None;
+            }
+        }
+
+        if self.rng.gen_bool(self.anomalies.unit_flip) {
+            telemetry.speed /= KM_PER_MILE;
+            telemetry.fuel_level *= 100.0;
+            self.injected.unit_flipped += 1;
+            trace_injection("unit_flip");
+        }
+
+        telemetry
+    }
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn trace_injection(kind: &'static str) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(kind, "anomaly injected");
+}
+
+impl<S: TelemetrySink, R: Rng> TelemetrySink for AnomalySink<S, R> {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        let telemetry = self.corrupt(telemetry);
+
+        if self.held.is_none() && self.rng.gen_bool(self.anomalies.out_of_order) {
+            self.held = Some(telemetry);
+            self.injected.out_of_order += 1;
+            trace_injection("out_of_order");
+            return Ok(());
+        }
+
+        self.inner.send(&telemetry)?;
+        if self.rng.gen_bool(self.anomalies.duplicate) {
+            self.inner.send(&telemetry)?;
+            self.injected.duplicated += 1;
+            trace_injection("duplicate");
+        }
+        if let Some(held) = self.held.take() {
+            self.inner.send(&held)?;
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let Some(held) = self.held.take() {
+            self.inner.send(&held)?;
+        }
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,154 @@
+use std::{fmt, io};
+
+use crate::{Telemetry, TelemetrySink};
+
+type Predicate = Box<dyn Fn(&Telemetry) -> bool + Send>;
+
+enum Check {
+    /// must hold for every record
+    Always,
+    /// must hold for at least one record
+    Eventually,
+    /// must hold for the last record
+    AtEnd,
+}
+
+struct Assertion {
+    name: String,
+    check: Check,
+    predicate: Predicate,
+    held: bool,
+}
+
+/// Expectations on a scenario, checked against its telemetry as it runs, e.g.
+/// `.always("speed never exceeds 120", |t| t.speed <= 120.0)`.
+///
+/// As a sink it fails on the first record breaking an `always` assertion, which stops a
+/// [`Simulator`](crate::Simulator) with the failure as its error. The rest are checked by
+/// [`Assertions::finish`], lend the simulator `&mut assertions` to call it after the run.
+#[derive(Default)]
+pub struct Assertions {
+    assertions: Vec<Assertion>,
+    updates: usize,
+    last: Option<Telemetry>,
+}
+
+impl Assertions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn always(
+        self,
+        name: impl Into<String>,
+        predicate: impl Fn(&Telemetry) -> bool + Send + 'static,
+    ) -> Self {
+        self.with(name, Check::Always, predicate)
+    }
+
+    pub fn eventually(
+        self,
+        name: impl Into<String>,
+        predicate: impl Fn(&Telemetry) -> bool + Send + 'static,
+    ) -> Self {
+        self.with(name, Check::Eventually, predicate)
+    }
+
+    pub fn at_end(
+        self,
+        name: impl Into<String>,
+        predicate: impl Fn(&Telemetry) -> bool + Send + 'static,
+    ) -> Self {
+        self.with(name, Check::AtEnd, predicate)
+    }
+
+    fn with(
+        mut self,
+        name: impl Into<String>,
+        check: Check,
+        predicate: impl Fn(&Telemetry) -> bool + Send + 'static,
+    ) -> Self {
+        self.assertions.push(Assertion {
+            name: name.into(),
+            check,
+            predicate: Box::new(predicate),
+            held: false,
+        });
+        self
+    }
+
+    /// Check a record, fails on the first `always` assertion it breaks
+    pub fn check(&mut self, telemetry: &Telemetry) -> Result<(), AssertionFailure> {
+        self.updates += 1;
+        for assertion in &mut self.assertions {
+            let holds = (assertion.predicate)(telemetry);
+            match assertion.check {
+                Check::Always if !holds => {
+                    return Err(AssertionFailure {
+                        name: assertion.name.clone(),
+                        update: Some(self.updates),
+                        telemetry: Some(Box::new(telemetry.clone())),
+                    })
+                }
+                Check::Eventually if holds => assertion.held = true,
+                _ => {}
+            }
+        }
+        self.last = Some(telemetry.clone());
+
+        Ok(())
+    }
+
+    /// End of the scenario, checks `eventually` and `at_end` assertions
+    pub fn finish(self) -> Result<(), AssertionFailure> {
+        for assertion in self.assertions {
+            let holds = match assertion.check {
+                Check::Always => true,
+                Check::Eventually => assertion.held,
+                Check::AtEnd => self.last.as_ref().is_some_and(|last| (assertion.predicate)(last)),
+            };
+            if !holds {
+                return Err(AssertionFailure {
+                    name: assertion.name,
+                    update: None,
+                    telemetry: self.last.map(Box::new),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TelemetrySink for Assertions {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        self.check(telemetry).map_err(io::Error::other)
+    }
+}
+
+/// Assertion that didn't hold, with the record that broke it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssertionFailure {
+    pub name: String,
+    /// update the assertion broke at, `None` when it failed at the end of the scenario
+    pub update: Option<usize>,
+    /// the breaking record, or the last one
+    pub telemetry: Option<Box<Telemetry>>,
+}
+
+impl fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.update {
+            Some(update) => write!(f, "\"{}\" failed at update {update}", self.name)?,
+            None => write!(f, "\"{}\" failed at the end", self.name)?,
+        }
+        if let Some(telemetry) = &self.telemetry {
+            let record = serde_json::to_string(telemetry).map_err(|_| fmt::Error)?;
+            write!(f, ", telemetry: {record}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for AssertionFailure {}
@@ -0,0 +1,111 @@
+use serde_json::{Map, Value};
+
+use crate::Telemetry;
+
+/// Signals reported together at their own rate
+#[derive(Debug, Clone)]
+pub struct SignalGroup {
+    pub name: String,
+    /// [`Telemetry`] field names
+    pub fields: Vec<String>,
+    /// report at least every `interval` seconds
+    pub interval: f64,
+    /// also report as soon as a numeric field moves by more than this, or any other field changes
+    pub on_delta: Option<f64>,
+}
+
+impl SignalGroup {
+    pub fn new(name: &str, fields: &[&str], interval: f64) -> Self {
+        Self {
+            name: name.to_owned(),
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            interval,
+            on_delta: None,
+        }
+    }
+
+    pub fn on_delta(mut self, delta: f64) -> Self {
+        self.on_delta = Some(delta);
+        self
+    }
+}
+
+/// Decides which signal groups are due each update instead of reporting everything every tick.
+/// Events aren't part of it, they are already available immediately through `Car::drain_events`.
+#[derive(Debug, Clone)]
+pub struct Cadence {
+    groups: Vec<SignalGroup>,
+    /// per group: seconds since it was last reported and the values it was reported with
+    state: Vec<(f64, Option<Map<String, Value>>)>,
+}
+
+impl Default for Cadence {
+    fn default() -> Self {
+        Self::new(vec![
+            SignalGroup::new("motion", &["speed", "rpm", "gear"], 1.0),
+            SignalGroup::new(
+                "controls",
+                &["accelerator_position", "brake_position", "clutch_position", "hand_brake"],
+                10.0,
+            )
+            .on_delta(0.05),
+            SignalGroup::new("fuel", &["fuel_level"], 10.0),
+            SignalGroup::new("status", &["drive_mode", "power_state"], 60.0).on_delta(0.0),
+        ])
+    }
+}
+
+impl Cadence {
+    pub fn new(groups: Vec<SignalGroup>) -> Self {
+        let state = vec![(0.0, None); groups.len()];
+        Self { groups, state }
+    }
+
+    /// Fields of all groups due with this update, `None` if nothing is. Call once per update with
+    /// the seconds it covered, i.e. the car's time step. The VIN is included with every report.
+    pub fn report(&mut self, telemetry: &Telemetry, time_step: f64) -> Option<Map<String, Value>> {
+        let Ok(Value::Object(mut source)) = serde_json::to_value(telemetry) else {
+            unreachable!("Telemetry always serializes into a map")
+        };
+
+        let mut report = Map::new();
+        for (group, (since, last)) in self.groups.iter().zip(self.state.iter_mut()) {
+            let values: Map<String, Value> = group
+                .fields
+                .iter()
+                .filter_map(|field| Some((field.clone(), source.get(field)?.clone())))
+                .collect();
+            *since += time_step;
+            let due = match (last.as_ref(), group.on_delta) {
+                (None, _) => true,
+                // Due on the update that brings it closest to the interval
+                _ if *since > group.interval - time_step / 2.0 => true,
+                (Some(last), Some(delta)) => changed(last, &values, delta),
+                (Some(_), None) => false,
+            };
+            if due {
+                report.extend(values.clone());
+                *since = 0.0;
+                *last = Some(values);
+            }
+        }
+
+        if report.is_empty() {
+            return None;
+        }
+        if let Some(vin) = source.remove("vin") {
+            report.insert("vin".to_owned(), vin);
+        }
+
+        Some(report)
+    }
+}
+
+fn changed(last: &Map<String, Value>, current: &Map<String, Value>, delta: f64) -> bool {
+    current.iter().any(|(field, value)| match (last.get(field), value) {
+        (Some(Value::Number(last)), Value::Number(value)) => {
+            (last.as_f64().unwrap_or_default() - value.as_f64().unwrap_or_default()).abs() > delta
+        }
+        (last, value) => last != Some(value),
+    })
+}
@@ -0,0 +1,105 @@
+use crate::{Car, CarConfig, Gear, InitialState};
+
+/// Search stops once steps shrink below this share of a parameter's value
+const MIN_STEP: f64 = 1e-4;
+const MAX_ITERATIONS: usize = 200;
+
+/// Driver inputs and the speed (in kmph) measured on the real vehicle after them, one per update
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceSample {
+    pub accelerator: f64,
+    pub brake: f64,
+    pub clutch: f64,
+    pub gear: Gear,
+    pub speed: f64,
+}
+
+/// Config values [`calibrate`] can adjust
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parameter {
+    FinalDrive,
+    SpeedSmoothing,
+    BrakingSmoothing,
+    BrakeGain,
+    Mass,
+    DragArea,
+}
+
+impl Parameter {
+    fn value(self, config: &mut CarConfig) -> &mut f64 {
+        match self {
+            Parameter::FinalDrive => &mut config.final_drive,
+            Parameter::SpeedSmoothing => &mut config.speed_smoothing,
+            Parameter::BrakingSmoothing => &mut config.braking_smoothing,
+            Parameter::BrakeGain => &mut config.brake_gain,
+            Parameter::Mass => &mut config.mass,
+            Parameter::DragArea => &mut config.drag_area,
+        }
+    }
+}
+
+/// Mean squared speed error of replaying the trace with the given config. The car starts from
+/// `initial` with the engine running, as it was while the trace was recorded.
+pub fn trace_error(trace: &[TraceSample], initial: &InitialState, config: &CarConfig) -> f64 {
+    let initial = InitialState { running: true, ..initial.clone() };
+    let mut car = Car::warm_start(initial, config.clone());
+    let error: f64 = trace
+        .iter()
+        .map(|sample| {
+            car.set_clutch_position(sample.clutch);
+            car.shift_gear(sample.gear);
+            // Without dual pedal control setting one pedal releases the other, so set the pressed
+            // one last
+            if sample.brake > 0.0 {
+                car.set_accelerator_position(sample.accelerator);
+                car.set_brake_position(sample.brake);
+            } else {
+                car.set_brake_position(sample.brake);
+                car.set_accelerator_position(sample.accelerator);
+            }
+            car.update();
+            (car.speed() - sample.speed).powi(2)
+        })
+        .sum();
+
+    error / trace.len().max(1) as f64
+}
+
+/// Adjust the given parameters of `config` so replaying the trace matches the measured speeds as
+/// closely as possible, by a simple pattern search starting from the current values
+pub fn calibrate(
+    trace: &[TraceSample],
+    initial: &InitialState,
+    mut config: CarConfig,
+    parameters: &[Parameter],
+) -> CarConfig {
+    let mut best = trace_error(trace, initial, &config);
+    // Relative step per parameter
+    let mut steps = vec![0.1; parameters.len()];
+
+    for _ in 0..MAX_ITERATIONS {
+        if steps.iter().all(|step| *step < MIN_STEP) {
+            break;
+        }
+        for (parameter, step) in parameters.iter().zip(&mut steps) {
+            let value = *parameter.value(&mut config);
+            let improved =
+                [value * (1.0 + *step), value * (1.0 - *step)].into_iter().any(|value| {
+                    let mut candidate = config.clone();
+                    *parameter.value(&mut candidate) = value;
+                    let error = trace_error(trace, initial, &candidate);
+                    if error < best {
+                        best = error;
+                        config = candidate;
+                        return true;
+                    }
+                    false
+                });
+            if !improved {
+                *step /= 2.0;
+            }
+        }
+    }
+
+    config
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+
+/// How urgently a chime needs the driver's attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum ChimePriority {
+    Low,
+    Medium,
+    High,
+}
+
+/// Audible warnings, raised as [`Event::Chime`](crate::Event::Chime) when their condition starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Chime {
+    /// moving with the seatbelt unfastened
+    Seatbelt,
+    /// fuel below [`LOW_FUEL`](crate::defaults::LOW_FUEL)
+    LowFuel,
+    /// moving with the hand brake pulled
+    HandBrakeOn,
+}
+
+impl Chime {
+    pub const ALL: [Chime; 3] = [Chime::Seatbelt, Chime::LowFuel, Chime::HandBrakeOn];
+
+    pub fn priority(self) -> ChimePriority {
+        match self {
+            Chime::Seatbelt => ChimePriority::Medium,
+            Chime::LowFuel => ChimePriority::Low,
+            Chime::HandBrakeOn => ChimePriority::High,
+        }
+    }
+}
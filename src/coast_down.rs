@@ -0,0 +1,69 @@
+use crate::{
+    defaults::{AIR_DENSITY, GRAVITY},
+    Car, CarConfig, Gear, HandBrake, InitialState, Surface,
+};
+
+/// Speeds (in kmph) the coast-down runs between, as in the usual road load test procedures
+const START_SPEED: f64 = 120.0;
+const END_SPEED: f64 = 15.0;
+
+/// Road load found by a [`coast_down`] test
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoastDown {
+    /// rolling resistance coefficient
+    pub rolling_resistance: f64,
+    /// drag coefficient times frontal area in m²
+    pub drag_area: f64,
+    /// speed in kmph at the start of each update and the deceleration over it in m/s²
+    pub samples: Vec<(f64, f64)>,
+}
+
+/// Let the car coast in neutral from 120 to 15 kmph on `surface` and fit the road load
+/// equation, deceleration = g * rolling resistance + ρ * drag area * v² / (2 * mass), to the
+/// speed trace. Use a short `config.time_step` for a finely sampled trace. `None` if the car
+/// doesn't slow down.
+pub fn coast_down(config: CarConfig, surface: Surface) -> Option<CoastDown> {
+    let initial = InitialState {
+        speed: START_SPEED,
+        gear: Gear::Neutral,
+        hand_brake: HandBrake::Disengaged,
+        running: true,
+        ..Default::default()
+    };
+    let mut car = Car::warm_start(initial, config);
+    car.set_surface(surface);
+
+    let time_step = car.config().time_step;
+    let mut samples = vec![];
+    // Bounded in case nothing slows the car
+    for _ in 0..100_000 {
+        if car.speed() <= END_SPEED {
+            break;
+        }
+        let speed = car.speed();
+        car.update();
+        samples.push((speed, (speed - car.speed()) / 3.6 / time_step));
+    }
+
+    // Least squares fit of deceleration against v²
+    let n = samples.len() as f64;
+    let squares = |(speed, _): &(f64, f64)| (speed / 3.6).powi(2);
+    let mean_x = samples.iter().map(squares).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|(_, deceleration)| deceleration).sum::<f64>() / n;
+    let (covariance, variance) =
+        samples.iter().fold((0.0, 0.0), |(covariance, variance), sample| {
+            let dx = squares(sample) - mean_x;
+            (covariance + dx * (sample.1 - mean_y), variance + dx * dx)
+        });
+    if samples.len() < 2 || variance == 0.0 || mean_y <= 0.0 {
+        return None;
+    }
+    let slope = covariance / variance;
+    let intercept = mean_y - slope * mean_x;
+
+    Some(CoastDown {
+        rolling_resistance: intercept / GRAVITY,
+        drag_area: 2.0 * slope * car.config().mass / (AIR_DENSITY * car.air_density()),
+        samples,
+    })
+}
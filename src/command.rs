@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{DriveMode, Gear, HandBrake, Heater, Surface};
+
+/// A single input to the car, the wire format for remote control paths and for recording and
+/// replaying input traces. Apply with [`Car::apply`](crate::Car::apply).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Command {
+    SetAccelerator(f64),
+    SetBrake(f64),
+    SetClutch(f64),
+    ShiftGear(Gear),
+    SetHandBrake(HandBrake),
+    TurnKey(bool),
+    Wake,
+    SetSeatbelt(bool),
+    SetDriveMode(DriveMode),
+    /// share of the tank to add
+    Refuel(f64),
+    ClearFault,
+    SetSirens(bool),
+    /// distance in m to an obstacle ahead, `None` when the road is clear
+    SetObstacleDistance(Option<f64>),
+    SetSurface(Surface),
+    /// in m above sea level
+    SetAltitude(f64),
+    /// rise over run, negative downhill
+    SetGradient(f64),
+    /// `None` switches the heater off
+    SetHeater(Option<Heater>),
+}
+
+#[cfg(feature = "schema")]
+impl Command {
+    /// JSON Schema of the JSON encoding
+    pub fn schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Command)).expect("schema is valid JSON")
+    }
+}
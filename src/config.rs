@@ -0,0 +1,377 @@
+use std::{
+    f64::consts::{LN_2, PI},
+    fmt,
+};
+
+#[cfg(feature = "rand")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{defaults::BSFC, AirBrakeConfig, Gear, HandBrake, StartInhibitors};
+
+/// Tunables for a [`Car`](crate::Car), use `Default::default()` for the stock behavior.
+#[derive(Debug, Clone)]
+pub struct CarConfig {
+    /// Allow accelerator and brake to be pressed together (left-foot braking, heel-toe).
+    /// The brake still wins: throttle is cut back to idle for as long as the brake is pressed.
+    pub dual_pedal: bool,
+    /// Applies in the Normal drive mode, Eco and Sport bring their own
+    pub pedal_map: PedalMap,
+    /// Accelerator travel past which the pedal clicks through the detent and demands full power,
+    /// whatever the pedal map. `None` disables kickdown.
+    pub kickdown: Option<f64>,
+    pub tire: Tire,
+    pub gear_ratios: GearRatios,
+    /// Reduction between transmission output and the wheels
+    pub final_drive: f64,
+    /// Simulated time each `Car::update` covers, in seconds
+    pub time_step: f64,
+    /// Longest physics step in seconds, longer time steps are split into sub-steps.
+    /// `None` runs the physics once per update.
+    pub max_step: Option<f64>,
+    /// Time constants of the speed and braking smoothing filters in seconds, so the filters
+    /// behave the same whatever the time step. 0.0 disables smoothing on that channel.
+    pub speed_smoothing: f64,
+    pub braking_smoothing: f64,
+    /// Units speed and distance are reported in through telemetry
+    pub units: Units,
+    /// Scales how hard the service brake bites, heavy vehicles need far longer to stop
+    pub brake_gain: f64,
+    /// Compressed air brakes instead of hydraulic ones
+    pub air_brakes: Option<AirBrakeConfig>,
+    /// Governed top speed in kmph
+    pub speed_limiter: Option<f64>,
+    /// Record driving and rest times
+    pub tachograph: bool,
+    /// Release the hand brake when the driver pulls away hard against it, otherwise the rear
+    /// brakes drag and heat up
+    pub hand_brake_auto_release: bool,
+    /// Electronic parking brake: applies itself once the car is parked in neutral or park with
+    /// the ignition off, and releases on driving away with the seatbelt fastened
+    pub electronic_parking_brake: bool,
+    /// Automatic emergency braking, brakes fully when an obstacle is about to be hit
+    pub aeb: bool,
+    /// Conditions turning the key checks before cranking
+    pub start_inhibitors: StartInhibitors,
+    /// Fuel tank size in litres
+    pub tank_capacity: f64,
+    /// Kerb weight with driver in kg
+    pub mass: f64,
+    /// Drag coefficient times frontal area in m²
+    pub drag_area: f64,
+    /// Brake specific fuel consumption of the engine in g/kWh, lower is more efficient
+    pub bsfc: f64,
+    /// Tire wear per km relative to the stock rate, above 1.0
+    /// [`MaintenanceItem::Tires`](crate::MaintenanceItem::Tires) falls due sooner
+    pub tire_wear: f64,
+    /// Seconds of driving efficiency figures are averaged over
+    pub efficiency_window: f64,
+    /// Ambulance, fire engine or police car: sirens can be switched on, which exempts the car
+    /// from the speed limiter
+    pub emergency_vehicle: bool,
+}
+
+impl Default for CarConfig {
+    fn default() -> Self {
+        Self {
+            dual_pedal: false,
+            pedal_map: PedalMap::default(),
+            kickdown: Some(0.95),
+            tire: Tire::Radius(0.4),
+            gear_ratios: GearRatios::default(),
+            final_drive: 10.0,
+            time_step: 1.0,
+            max_step: None,
+            // Same as the filters' old fixed alpha of 0.5 at one update per second
+            speed_smoothing: 1.0 / LN_2,
+            braking_smoothing: 1.0 / LN_2,
+            units: Units::Metric,
+            brake_gain: 1.0,
+            air_brakes: None,
+            speed_limiter: None,
+            tachograph: false,
+            hand_brake_auto_release: false,
+            electronic_parking_brake: false,
+            aeb: true,
+            start_inhibitors: StartInhibitors::default(),
+            tank_capacity: 40.0,
+            mass: 1300.0,
+            drag_area: 0.65,
+            bsfc: BSFC,
+            tire_wear: 1.0,
+            efficiency_window: 60.0,
+            emergency_vehicle: false,
+        }
+    }
+}
+
+impl CarConfig {
+    /// Mid-size sedan, the same as `CarConfig::default()`
+    pub fn sedan() -> Self {
+        Self::default()
+    }
+
+    /// Small hatchback: smaller wheels, shorter gearing and lighter, so the brakes bite harder
+    pub fn hatchback() -> Self {
+        Self {
+            tire: Tire::Size { width: 185.0, aspect_ratio: 65.0, rim: 15.0 },
+            gear_ratios: GearRatios {
+                first: 3.55,
+                second: 2.1,
+                third: 1.35,
+                fourth: 1.03,
+                fifth: 0.8,
+                reverse: 10.0,
+            },
+            final_drive: 8.0,
+            brake_gain: 1.2,
+            ..Default::default()
+        }
+    }
+
+    /// Bus or heavy truck: tall gearing on large tires, air brakes, 80 kmph limiter and a tachograph
+    pub fn heavy_vehicle() -> Self {
+        Self {
+            kickdown: None,
+            tire: Tire::Size { width: 295.0, aspect_ratio: 80.0, rim: 22.5 },
+            gear_ratios: GearRatios {
+                first: 6.0,
+                second: 3.5,
+                third: 2.2,
+                fourth: 1.4,
+                fifth: 1.0,
+                reverse: 6.0,
+            },
+            brake_gain: 0.4,
+            air_brakes: Some(AirBrakeConfig::default()),
+            speed_limiter: Some(80.0),
+            tachograph: true,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    /// Copy with mass, drag area, engine efficiency, tire wear rate, brake gain and tank size each
+    /// scaled by a random factor within ±`spread`, e.g. 0.05, so cars built from one config don't
+    /// all produce the same trace.
+    /// Factors are drawn from an RNG seeded with `seed`, e.g. the vehicle's index. Spreads are
+    /// capped at 0.9 so values stay positive, a NaN or infinite spread leaves the config as is.
+    pub fn jittered(&self, spread: f64, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let spread = if spread.is_finite() { spread.abs().min(0.9) } else { 0.0 };
+        let mut jitter = |value: f64| match spread {
+            0.0 => value,
+            spread => value * (1.0 + rng.gen_range(-spread..=spread)),
+        };
+
+        Self {
+            mass: jitter(self.mass),
+            drag_area: jitter(self.drag_area),
+            bsfc: jitter(self.bsfc),
+            tire_wear: jitter(self.tire_wear),
+            brake_gain: jitter(self.brake_gain),
+            tank_capacity: jitter(self.tank_capacity),
+            ..self.clone()
+        }
+    }
+}
+
+pub const KM_PER_MILE: f64 = 1.609344;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Units {
+    /// kmph and km
+    #[default]
+    Metric,
+    /// mph and miles
+    Imperial,
+}
+
+impl Units {
+    /// Convert a speed in kmph
+    pub fn speed(&self, kmph: f64) -> f64 {
+        match self {
+            Units::Metric => kmph,
+            Units::Imperial => kmph / KM_PER_MILE,
+        }
+    }
+
+    /// Convert a distance in km
+    pub fn distance(&self, km: f64) -> f64 {
+        match self {
+            Units::Metric => km,
+            Units::Imperial => km / KM_PER_MILE,
+        }
+    }
+}
+
+/// State a car starts in and returns to on [`Car::reset`](crate::Car::reset), e.g. already
+/// cruising in fourth to start a test mid-scenario
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialState {
+    pub fuel_level: f64,
+    /// in kmph
+    pub speed: f64,
+    pub gear: Gear,
+    pub hand_brake: HandBrake,
+    /// engine already running
+    pub running: bool,
+}
+
+impl InitialState {
+    pub fn validate(&self) -> Result<(), InvalidFuelLevel> {
+        if !(0.0..=1.0).contains(&self.fuel_level) {
+            return Err(InvalidFuelLevel(self.fuel_level));
+        }
+
+        Ok(())
+    }
+}
+
+/// Fuel level given as something other than a share of the tank, e.g. a percentage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidFuelLevel(pub f64);
+
+impl fmt::Display for InvalidFuelLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fuel level {} is outside 0.0..=1.0", self.0)
+    }
+}
+
+impl std::error::Error for InvalidFuelLevel {}
+
+impl Default for InitialState {
+    fn default() -> Self {
+        Self {
+            fuel_level: 1.0,
+            speed: 0.0,
+            gear: Gear::Neutral,
+            hand_brake: HandBrake::Full,
+            running: false,
+        }
+    }
+}
+
+/// Gearbox ratios as listed on datasheets, i.e. engine revolutions per transmission output revolution
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GearRatios {
+    pub first: f64,
+    pub second: f64,
+    pub third: f64,
+    pub fourth: f64,
+    pub fifth: f64,
+    pub reverse: f64,
+}
+
+impl Default for GearRatios {
+    fn default() -> Self {
+        Self { first: 3.33, second: 2.0, third: 1.25, fourth: 1.0, fifth: 0.71, reverse: 10.0 }
+    }
+}
+
+impl GearRatios {
+    /// `None` in neutral and park, where the engine isn't connected to the wheels
+    pub fn ratio(&self, gear: &Gear) -> Option<f64> {
+        match gear {
+            Gear::Neutral | Gear::Park => None,
+            Gear::First => Some(self.first),
+            Gear::Second => Some(self.second),
+            Gear::Third => Some(self.third),
+            Gear::Fourth => Some(self.fourth),
+            Gear::Fifth => Some(self.fifth),
+            Gear::Reverse => Some(self.reverse),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Tire {
+    /// rolling radius in m
+    Radius(f64),
+    /// sidewall markings, e.g. 205/55 R16 is `Size { width: 205.0, aspect_ratio: 55.0, rim: 16.0 }`
+    Size {
+        /// in mm
+        width: f64,
+        /// sidewall height as a percentage of width
+        aspect_ratio: f64,
+        /// in inches
+        rim: f64,
+    },
+}
+
+impl Tire {
+    /// in m
+    pub fn rolling_radius(&self) -> f64 {
+        match self {
+            Tire::Radius(radius) => *radius,
+            Tire::Size { width, aspect_ratio, rim } => {
+                (rim * 25.4 / 2.0 + width * aspect_ratio / 100.0) / 1000.0
+            }
+        }
+    }
+
+    /// in m
+    pub fn circumference(&self) -> f64 {
+        2.0 * PI * self.rolling_radius()
+    }
+}
+
+/// Curve translating accelerator travel into engine demand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub enum PedalMap {
+    /// progressive and capped at 80% demand, only kickdown gets full power
+    Eco,
+    #[default]
+    Normal,
+    /// most of the demand comes in the first half of travel
+    Sport,
+}
+
+impl PedalMap {
+    pub fn demand(&self, position: f64) -> f64 {
+        let position = position.clamp(0.0, 1.0);
+        match self {
+            PedalMap::Eco => 0.8 * position.powi(2),
+            PedalMap::Normal => position,
+            PedalMap::Sport => 1.0 - (1.0 - position).powi(2),
+        }
+    }
+}
+
+/// Driver selectable mode trading performance for economy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DriveMode {
+    Eco,
+    #[default]
+    Normal,
+    Sport,
+}
+
+impl DriveMode {
+    /// in kW, Normal holds back the last of the engine's power that Sport unlocks
+    pub fn max_power(&self) -> f64 {
+        match self {
+            DriveMode::Eco => 70.0,
+            DriveMode::Normal => 90.0,
+            DriveMode::Sport => crate::defaults::MAX_POWER,
+        }
+    }
+
+    pub fn pedal_map(&self) -> PedalMap {
+        match self {
+            DriveMode::Eco => PedalMap::Eco,
+            DriveMode::Normal => PedalMap::Normal,
+            DriveMode::Sport => PedalMap::Sport,
+        }
+    }
+
+    /// in kmph, `None` when only the drivetrain limits speed
+    pub fn top_speed(&self) -> Option<f64> {
+        match self {
+            DriveMode::Eco => Some(90.0),
+            DriveMode::Normal | DriveMode::Sport => None,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(feature = "simulator")]
+use std::collections::VecDeque;
+
+#[cfg(feature = "simulator")]
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{Car, ControlError, Gear, HandBrake, Telemetry};
+
+/// Commands from an external controller, inputs left at `None` keep their current value
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Actuation {
+    pub accelerator: Option<f64>,
+    pub brake: Option<f64>,
+    pub clutch: Option<f64>,
+    pub gear: Option<Gear>,
+    pub hand_brake: Option<HandBrake>,
+}
+
+impl Actuation {
+    /// Validated like any other [`Controls`](crate::Controls), nothing is applied if it fails
+    pub fn apply(self, car: &mut Car) -> Result<(), ControlError> {
+        let mut controls = car.controls();
+        if let Some(position) = self.accelerator {
+            controls = controls.accelerator(position);
+        }
+        if let Some(position) = self.brake {
+            controls = controls.brake(position);
+        }
+        if let Some(position) = self.clutch {
+            controls = controls.clutch(position);
+        }
+        if let Some(gear) = self.gear {
+            controls = controls.gear(gear);
+        }
+        if let Some(position) = self.hand_brake {
+            controls = controls.hand_brake(position);
+        }
+
+        controls.apply()
+    }
+}
+
+/// Logic under test in a software-in-the-loop setup, e.g. an ECU's control loop behind FFI or
+/// a remote service. It only sees what the car's sensors report and acts through actuators,
+/// unlike a [`Driver`](crate::Driver) that gets the whole car.
+pub trait Controller: Send {
+    fn control(&mut self, sensors: &Telemetry) -> Actuation;
+}
+
+impl<F: FnMut(&Telemetry) -> Actuation + Send> Controller for F {
+    fn control(&mut self, sensors: &Telemetry) -> Actuation {
+        self(sensors)
+    }
+}
+
+/// Delay between a controller commanding an actuation and it taking effect, as on a real
+/// actuator bus. Actuations queue up and take effect in the order they were commanded, at the
+/// first tick after they're due.
+#[cfg(feature = "simulator")]
+#[derive(Debug, Clone)]
+pub struct ActuatorLag {
+    /// in s of simulated time
+    pub delay: f64,
+    /// largest deviation from `delay` in s, drawn uniformly
+    pub jitter: f64,
+    rng: StdRng,
+    /// actuations and when they take effect
+    pending: VecDeque<(f64, Actuation)>,
+    time: f64,
+}
+
+#[cfg(feature = "simulator")]
+impl ActuatorLag {
+    /// Jitter is drawn from an RNG seeded with `seed`, so runs can be repeated
+    pub fn new(delay: f64, jitter: f64, seed: u64) -> Self {
+        Self {
+            delay,
+            jitter,
+            rng: StdRng::seed_from_u64(seed),
+            pending: VecDeque::new(),
+            time: 0.0,
+        }
+    }
+
+    pub(crate) fn command(&mut self, actuation: Actuation) {
+        let jitter = match self.jitter {
+            jitter if jitter > 0.0 => self.rng.gen_range(-jitter..=jitter),
+            _ => 0.0,
+        };
+        let due = self.time + (self.delay + jitter).max(0.0);
+        // Can't overtake an earlier command
+        let due = self.pending.back().map_or(due, |(last, _)| due.max(*last));
+        self.pending.push_back((due, actuation));
+    }
+
+    /// Next actuation that has to take effect by now
+    pub(crate) fn next_due(&mut self) -> Option<Actuation> {
+        match self.pending.front() {
+            Some((due, _)) if *due <= self.time => self.pending.pop_front().map(|(_, a)| a),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn advance(&mut self, time_step: f64) {
+        self.time += time_step;
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use crate::{Car, Gear, HandBrake};
+
+#[derive(Debug, PartialEq)]
+pub enum ControlError {
+    /// accelerator and brake pressed at the same time without `CarConfig::dual_pedal`
+    BothPedals,
+    OutOfRange {
+        control: &'static str,
+        position: f64,
+    },
+}
+
+impl fmt::Display for ControlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ControlError::BothPedals => write!(f, "accelerator and brake pressed together"),
+            ControlError::OutOfRange { control, position } => {
+                write!(f, "{control} position {position} is outside 0.0..=1.0")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ControlError {}
+
+/// Collects driver inputs and applies them to the car in one go, only if the
+/// resulting combination is valid. Inputs that aren't set keep their current value.
+pub struct Controls<'a> {
+    car: &'a mut Car,
+    accelerator: Option<f64>,
+    brake: Option<f64>,
+    clutch: Option<f64>,
+    gear: Option<Gear>,
+    hand_brake: Option<HandBrake>,
+}
+
+impl<'a> Controls<'a> {
+    pub(crate) fn new(car: &'a mut Car) -> Self {
+        Self { car, accelerator: None, brake: None, clutch: None, gear: None, hand_brake: None }
+    }
+
+    pub fn accelerator(mut self, position: f64) -> Self {
+        self.accelerator = Some(position);
+        self
+    }
+
+    pub fn brake(mut self, position: f64) -> Self {
+        self.brake = Some(position);
+        self
+    }
+
+    pub fn clutch(mut self, position: f64) -> Self {
+        self.clutch = Some(position);
+        self
+    }
+
+    pub fn gear(mut self, gear: Gear) -> Self {
+        self.gear = Some(gear);
+        self
+    }
+
+    pub fn hand_brake(mut self, position: HandBrake) -> Self {
+        self.hand_brake = Some(position);
+        self
+    }
+
+    pub fn apply(self) -> Result<(), ControlError> {
+        let accelerator = self.accelerator.unwrap_or(self.car.accelerator_position);
+        let brake = self.brake.unwrap_or(self.car.brake_position);
+        let clutch = self.clutch.unwrap_or(self.car.clutch_position);
+
+        for (control, position) in
+            [("accelerator", accelerator), ("brake", brake), ("clutch", clutch)]
+        {
+            if !(0.0..=1.0).contains(&position) {
+                return Err(ControlError::OutOfRange { control, position });
+            }
+        }
+
+        if accelerator > 0.0 && brake > 0.0 && !self.car.config.dual_pedal {
+            return Err(ControlError::BothPedals);
+        }
+
+        self.car.accelerator_position = accelerator;
+        self.car.brake_position = brake;
+        self.car.clutch_position = clutch;
+        if let Some(gear) = self.gear {
+            self.car.shift_gear(gear);
+        }
+        if let Some(hand_brake) = self.hand_brake {
+            self.car.set_handbrake_position(hand_brake);
+        }
+
+        Ok(())
+    }
+}
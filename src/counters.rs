@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Lifetime usage counters, like an hour-meter they survive [`Car::reset`](crate::Car::reset)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Counters {
+    /// times the key was turned on
+    pub key_cycles: u32,
+    /// key cycles in which the car moved
+    pub drive_sessions: u32,
+    /// engine running time in h
+    pub engine_hours: f64,
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use crate::{
+    defaults::{LOW_FUEL, MAX_RPM},
+    Car, Units,
+};
+
+const BOLD: &str = "\x1b[1m";
+const YELLOW: &str = "\x1b[33m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// Human readable view of a car's state, one field per line or on a single line with
+/// [`Dashboard::compact`]. `Car` displays as the multi-line dashboard, or the compact one
+/// with `{:#}`.
+pub struct Dashboard<'a> {
+    car: &'a Car,
+    compact: bool,
+    color: bool,
+}
+
+impl<'a> Dashboard<'a> {
+    pub(crate) fn new(car: &'a Car) -> Self {
+        Self { car, compact: false, color: false }
+    }
+
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Highlight values with ANSI escape codes, rpm and fuel turn yellow then red as they
+    /// near the redline or run low
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn paint(&self, value: impl fmt::Display, warning: Option<&str>) -> String {
+        match (self.color, warning) {
+            (false, _) => value.to_string(),
+            (true, None) => format!("{BOLD}{value}{RESET}"),
+            (true, Some(color)) => format!("{BOLD}{color}{value}{RESET}"),
+        }
+    }
+}
+
+impl fmt::Display for Dashboard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let car = self.car;
+        let units = car.config.units;
+        let (speed_unit, distance_unit) = match units {
+            Units::Metric => ("kmph", "km"),
+            Units::Imperial => ("mph", "mi"),
+        };
+
+        let rpm_warning = match car.rpm() as f64 / MAX_RPM {
+            share if share > 0.9 => Some(RED),
+            share if share > 0.75 => Some(YELLOW),
+            _ => None,
+        };
+        let fuel_warning = match car.fuel_level() {
+            level if level < LOW_FUEL => Some(RED),
+            level if level < 0.25 => Some(YELLOW),
+            _ => None,
+        };
+
+        let speed = self.paint(format_args!("{:.1} {speed_unit}", units.speed(car.speed())), None);
+        let rpm = self.paint(car.rpm(), rpm_warning);
+        let gear = self.paint(format_args!("{:?}", car.gear()), None);
+        let fuel = self.paint(format_args!("{:.0}%", car.fuel_percent()), fuel_warning);
+        let distance =
+            self.paint(format_args!("{:.2} {distance_unit}", units.distance(car.odometer())), None);
+
+        if self.compact {
+            return write!(
+                f,
+                "{speed} | {rpm} rpm | {gear} | fuel {fuel} | {distance} | {:?} {:?}",
+                car.drive_mode(),
+                car.power_state()
+            );
+        }
+
+        if let Some(identity) = car.identity() {
+            writeln!(
+                f,
+                "Vehicle: {} {} {} ({})",
+                identity.year, identity.make, identity.model, identity.vin
+            )?;
+        }
+        writeln!(f, "Speed: {speed}")?;
+        writeln!(f, "Fuel: {fuel}")?;
+        writeln!(f, "Gear: {gear}")?;
+        writeln!(f, "RPM: {rpm}")?;
+        writeln!(f, "Accelerator: {:0.2}", car.accelerator_position())?;
+        writeln!(f, "Brake: {:0.2}", car.brake_position())?;
+        writeln!(f, "Clutch: {:0.2}", car.clutch_position())?;
+        writeln!(f, "Hand brake: {:?}", car.hand_brake())?;
+        writeln!(f, "Drive mode: {:?}", car.drive_mode())?;
+        writeln!(f, "Power state: {:?}", car.power_state())?;
+        write!(f, "Distance travelled: {distance}")
+    }
+}
+
+impl fmt::Display for Car {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.dashboard().compact(f.alternate()).fmt(f)
+    }
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+use crate::Deceleration;
+
+/// Intermediate values of the last physics step, for seeing why a config makes the car behave
+/// the way it does
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DebugState {
+    /// engine demand after the pedal map, kickdown, limp mode and altitude, 0.0..=1.0
+    pub throttle: f64,
+    /// in kW, what fuel consumption is worked out from
+    pub engine_power: f64,
+    /// speed in kmph the driveline turns the wheels at
+    pub wheel_speed: f64,
+    /// speed in kmph before smoothing
+    pub raw_speed: f64,
+    /// kmph lost to the engine this step when coasting in gear
+    pub engine_drag: f64,
+    /// rolling resistance and aerodynamic drag, in m/s²
+    pub road_deceleration: f64,
+    /// braking effort before and after smoothing
+    pub raw_braking: f64,
+    pub effective_braking: f64,
+    pub deceleration: Deceleration,
+    /// weight of a new sample in the speed and braking filters
+    pub speed_alpha: f64,
+    pub braking_alpha: f64,
+    /// samples the filters are holding
+    pub speed_filter: Vec<f64>,
+    pub braking_filter: Vec<f64>,
+}
@@ -0,0 +1,62 @@
+//! Baseline figures of the simulated car. The engine and brake constants apply to every car,
+//! whatever its [`CarConfig`](crate::CarConfig).
+
+/// Idle speed
+pub const BASE_RPM: f64 = 750.0;
+/// Redline
+pub const MAX_RPM: f64 = 5000.0;
+/// Peak engine power in kW
+pub const MAX_POWER: f64 = 100.0;
+/// Peak engine torque in Nm
+pub const MAX_TORQUE: f64 = 200.0;
+/// Brake specific fuel consumption in g/kWh
+pub const BSFC: f64 = 180.0;
+/// Petrol in kg/l
+pub const FUEL_DENSITY: f64 = 0.745;
+/// In kW, what the engine loses to friction at idle, growing in step with RPM
+pub const FRICTION_POWER: f64 = 2.0;
+/// Share of engine speed the pedal's setting sags by when the road takes full power
+pub const ENGINE_DROOP: f64 = 0.1;
+/// kmph lost per second when coasting in gear, per unit of gear ratio
+pub const ENGINE_BRAKING: f64 = 1.5;
+/// In °C, the temperature brakes cool down to
+pub const AMBIENT_TEMPERATURE: f64 = 20.0;
+/// °C per second, driving at full throttle against a full hand brake
+pub const BRAKE_HEATING: f64 = 15.0;
+/// Share of the heat above ambient lost per second
+pub const BRAKE_COOLING: f64 = 0.02;
+/// In °C, raises [`Event::BrakeOverheat`](crate::Event::BrakeOverheat)
+pub const BRAKE_OVERHEAT: f64 = 350.0;
+/// In °C, the hand brake starts fading at the lower end and holds 30% at the upper end
+pub const BRAKE_FADE: (f64, f64) = (300.0, 600.0);
+/// Share of the tank left when the low fuel warning comes on
+pub const LOW_FUEL: f64 = 0.1;
+/// Accelerator position counted as pulling away against the hand brake
+pub const DRIVE_AWAY_THROTTLE: f64 = 0.5;
+/// In seconds, emergency braking kicks in when an obstacle is closer than this
+pub const AEB_TIME_TO_COLLISION: f64 = 1.5;
+/// In m, air gets thinner by a factor of e every scale height, taking engine power and
+/// brake cooling with it
+pub const SCALE_HEIGHT: f64 = 8500.0;
+/// Standard gravity in m/s²
+pub const GRAVITY: f64 = 9.81;
+/// Air density at sea level in kg/m³
+pub const AIR_DENSITY: f64 = 1.225;
+/// Deceleration of the service brakes fully applied while coasting, in m/s²
+pub const BRAKE_DECELERATION: f64 = 9.0;
+/// Share of the vehicle speed the driven wheels may run ahead by while accelerating
+pub const MAX_TRACTION_SLIP: f64 = 0.1;
+/// Share of the vehicle speed braked wheels fall behind by just short of locking up
+pub const MAX_BRAKE_SLIP: f64 = 0.2;
+/// Share of full power available in limp mode
+pub const LIMP_POWER: f64 = 0.3;
+/// Top speed in limp mode, in kmph
+pub const LIMP_SPEED: f64 = 50.0;
+/// In kmph, the park pawl ratchets over the gear above this
+pub const PARK_MAX_SPEED: f64 = 5.0;
+/// m/s² RMS at max RPM under full load
+pub const ENGINE_VIBRATION: f64 = 1.5;
+/// m/s² RMS per 100 kmph
+pub const ROAD_VIBRATION: f64 = 0.8;
+/// Share of a road shock left after a second
+pub const SHOCK_DECAY: f64 = 0.5;
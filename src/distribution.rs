@@ -0,0 +1,85 @@
+use rand::Rng;
+
+/// Distribution of a random driver choice, sample with `rng.sample(&distribution)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Constant(f64),
+    Uniform {
+        low: f64,
+        high: f64,
+    },
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+    /// scaled from 0.0..1.0 to `low..high`, skews choices towards either end
+    Beta {
+        alpha: f64,
+        beta: f64,
+        low: f64,
+        high: f64,
+    },
+    Exponential {
+        mean: f64,
+    },
+}
+
+impl Distribution {
+    /// Draw a value and clamp it to `min..=max`, e.g. to keep a pedal position valid
+    pub fn sample_clamped(&self, rng: &mut impl Rng, min: f64, max: f64) -> f64 {
+        rng.sample(self).clamp(min, max)
+    }
+}
+
+impl rand::distributions::Distribution<f64> for Distribution {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Distribution::Constant(value) => value,
+            Distribution::Uniform { low, high } if low < high => rng.gen_range(low..high),
+            Distribution::Uniform { low, .. } => low,
+            Distribution::Normal { mean, std_dev } => mean + std_dev * standard_normal(rng),
+            Distribution::Beta { alpha, beta, low, high } => {
+                let x = gamma(rng, alpha);
+                let y = gamma(rng, beta);
+                low + (high - low) * x / (x + y)
+            }
+            Distribution::Exponential { mean } => -mean * (1.0 - rng.gen::<f64>()).ln(),
+        }
+    }
+}
+
+/// True with probability `chance`, clamped to 0.0..=1.0 with NaN counting as 0.0, so a bad
+/// configuration can't make [`Rng::gen_bool`] panic
+pub(crate) fn happens(rng: &mut (impl Rng + ?Sized), chance: f64) -> bool {
+    let chance = if chance.is_nan() { 0.0 } else { chance.clamp(0.0, 1.0) };
+    rng.gen_bool(chance)
+}
+
+/// Box-Muller transform
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Marsaglia and Tsang's method, boosted for shapes below 1.0
+fn gamma<R: Rng + ?Sized>(rng: &mut R, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let u = 1.0 - rng.gen::<f64>();
+        return gamma(rng, shape + 1.0) * u.powf(1.0 / shape);
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = 1.0 - rng.gen::<f64>();
+        if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
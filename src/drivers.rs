@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Accumulated per driver, across all their trips
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct DriverStats {
+    /// in km
+    pub distance: f64,
+    /// time spent moving, in s
+    pub driving_time: f64,
+    /// share of the tank
+    pub fuel_used: f64,
+}
+
+/// Driver profiles bound to key fobs, the last key used picks the active driver
+#[derive(Debug, Clone, Default)]
+pub struct Drivers {
+    keys: HashMap<u32, String>,
+    active: Option<String>,
+    stats: HashMap<String, DriverStats>,
+}
+
+impl Drivers {
+    pub fn active(&self) -> Option<&str> {
+        self.active.as_deref()
+    }
+
+    pub fn stats(&self, driver: &str) -> Option<&DriverStats> {
+        self.stats.get(driver)
+    }
+
+    pub(crate) fn register(&mut self, key: u32, driver: String) {
+        self.keys.insert(key, driver);
+    }
+
+    /// Unknown keys still work, but nobody gets attributed
+    pub(crate) fn select(&mut self, key: u32) -> bool {
+        self.active = self.keys.get(&key).cloned();
+        self.active.is_some()
+    }
+
+    pub(crate) fn record(&mut self, distance: f64, driving_time: f64, fuel_used: f64) {
+        let Some(driver) = &self.active else { return };
+        let stats = self.stats.entry(driver.clone()).or_default();
+        stats.distance += distance;
+        stats.driving_time += driving_time;
+        stats.fuel_used += fuel_used;
+    }
+
+    /// Keep the keys, forget who drove what
+    pub(crate) fn take_keys(&mut self) -> Self {
+        Self { keys: std::mem::take(&mut self.keys), ..Default::default() }
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+use rand::Rng;
+
+use crate::{Car, Event, Tachograph};
+
+/// Extra reaction time (in s) of a fully drowsy driver
+const MAX_REACTION_DELAY: f64 = 2.0;
+/// Pedal jitter of a fully drowsy driver, as a fraction of pedal travel
+const MAX_PEDAL_JITTER: f64 = 0.2;
+/// Drowsiness level above which a DrowsinessSuspected event is raised
+const SUSPICION_LEVEL: f64 = 0.5;
+
+/// Degrades a driver's pedal inputs the longer they drive without a break, delaying and
+/// roughening them before they reach the car
+#[derive(Debug, Clone)]
+pub struct Drowsiness {
+    /// continuous driving (in h) before fatigue sets in
+    pub onset: f64,
+    /// further driving (in h) until the driver is fully drowsy
+    pub ramp: f64,
+    tachograph: Tachograph,
+    pending: VecDeque<(f64, f64)>,
+    suspected: bool,
+}
+
+impl Default for Drowsiness {
+    fn default() -> Self {
+        Self::new(2.0, 2.5)
+    }
+}
+
+impl Drowsiness {
+    pub fn new(onset: f64, ramp: f64) -> Self {
+        Self {
+            onset,
+            ramp,
+            tachograph: Tachograph::default(),
+            pending: VecDeque::new(),
+            suspected: false,
+        }
+    }
+
+    /// 0.0 when alert, 1.0 when fully drowsy
+    pub fn level(&self) -> f64 {
+        let hours = self.tachograph.continuous_driving / 3600.0;
+        ((hours - self.onset) / self.ramp).clamp(0.0, 1.0)
+    }
+
+    /// Call once per update with what an alert driver would do, the car gets what this driver
+    /// actually does
+    pub fn press_pedals(
+        &mut self,
+        car: &mut Car,
+        accelerator: f64,
+        brake: f64,
+        rng: &mut impl Rng,
+    ) {
+        let time_step = car.config().time_step;
+        self.tachograph.update(car.speed() != 0.0, time_step);
+
+        let level = self.level();
+        if level >= SUSPICION_LEVEL && !self.suspected {
+            car.emit(Event::DrowsinessSuspected {
+                continuous_driving: self.tachograph.continuous_driving,
+            });
+        }
+        self.suspected = level >= SUSPICION_LEVEL;
+
+        let jitter = level * MAX_PEDAL_JITTER;
+        let mut roughen = |position: f64| match position {
+            p if p > 0.0 && jitter > 0.0 => (p + rng.gen_range(-jitter..jitter)).clamp(0.0, 1.0),
+            p => p,
+        };
+        self.pending.push_back((roughen(accelerator), roughen(brake)));
+
+        let delay = (level * MAX_REACTION_DELAY / time_step).round() as usize;
+        while self.pending.len() > delay + 1 {
+            self.pending.pop_front();
+        }
+        let Some(&(accelerator, brake)) = self.pending.front() else { return };
+        if brake > 0.0 {
+            car.set_brake_position(brake);
+        } else {
+            car.set_brake_position(0.0);
+            car.set_accelerator_position(accelerator);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    defaults::BASE_RPM, efficiency::FUEL_ENERGY, Car, CarConfig, Gear, HandBrake, InitialState,
+};
+
+/// Updates given to the car to settle at a speed
+const SETTLE_UPDATES: usize = 30;
+/// Updates the fuel used is measured over once settled
+const MEASURE_UPDATES: usize = 60;
+/// Share of the commanded speed the held speed may be off by
+const TOLERANCE: f64 = 0.01;
+
+/// Steady state of the car held at one speed on a [`dyno`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DynoPoint {
+    /// in kmph
+    pub speed: f64,
+    pub gear: Gear,
+    /// pedal position that holds the speed
+    pub accelerator: f64,
+    pub rpm: u32,
+    /// power the engine puts through the wheels at this speed and gradient, in kW
+    pub road_load: f64,
+    /// rate energy is drawn from the fuel, in kW
+    pub power: f64,
+    pub litres_per_hour: f64,
+    pub litres_per_100km: f64,
+}
+
+/// Hold the car at each of `speeds` (in kmph) up a `gradient` (rise over run, negative downhill),
+/// in the tallest gear that doesn't lug the engine below idle, to map consumption across speeds.
+/// Consumption is the fuel the car burns while holding the speed. Speeds the car can't hold are
+/// left out.
+pub fn dyno(
+    config: CarConfig,
+    gradient: f64,
+    speeds: impl IntoIterator<Item = f64>,
+) -> Vec<DynoPoint> {
+    speeds.into_iter().filter_map(|speed| hold(&config, gradient, speed)).collect()
+}
+
+fn hold(config: &CarConfig, gradient: f64, speed: f64) -> Option<DynoPoint> {
+    let idle_speed = |gear: &Gear| {
+        let ratio = gear.ratio(&config.gear_ratios)?;
+        Some(BASE_RPM / ratio / config.final_drive * config.tire.circumference() * 60.0 / 1000.0)
+    };
+    let gear = [Gear::Fifth, Gear::Fourth, Gear::Third, Gear::Second, Gear::First]
+        .into_iter()
+        .find(|gear| idle_speed(gear).is_some_and(|idle| idle <= speed))?;
+
+    let settle = |accelerator: f64| {
+        let initial = InitialState {
+            speed,
+            gear,
+            hand_brake: HandBrake::Disengaged,
+            running: true,
+            ..Default::default()
+        };
+        let mut car = Car::warm_start(initial, config.clone());
+        car.set_gradient(gradient);
+        car.set_accelerator_position(accelerator);
+        for _ in 0..SETTLE_UPDATES {
+            car.update();
+        }
+        car
+    };
+
+    // Steady speed only goes up with the accelerator, so bisect for the position holding it
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..40 {
+        let middle = (low + high) / 2.0;
+        if settle(middle).speed() < speed {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    let mut car = settle(high);
+    if (car.speed() - speed).abs() > speed * TOLERANCE {
+        return None;
+    }
+
+    let fuel_level = car.fuel_level();
+    for _ in 0..MEASURE_UPDATES {
+        car.update();
+    }
+    let litres = (fuel_level - car.fuel_level()) * config.tank_capacity;
+    let hours = MEASURE_UPDATES as f64 * config.time_step / 3600.0;
+    let litres_per_hour = litres / hours;
+
+    Some(DynoPoint {
+        speed: car.speed(),
+        gear,
+        accelerator: high,
+        rpm: car.rpm(),
+        road_load: car.wheel_power,
+        power: litres_per_hour * FUEL_ENERGY,
+        litres_per_hour,
+        litres_per_100km: litres_per_hour * 100.0 / car.speed(),
+    })
+}
@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::KM_PER_MILE;
+
+/// Energy in a litre of petrol, in kWh
+pub(crate) const FUEL_ENERGY: f64 = 8.9;
+/// Energy the EPA equates to a US gallon of petrol, in kWh
+const KWH_PER_GALLON_EQUIVALENT: f64 = 33.7;
+
+/// Energy efficiency over the last `CarConfig::efficiency_window` seconds, in the units
+/// consumers compare across fuel and electric vehicles
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Efficiency {
+    pub wh_per_km: f64,
+    pub km_per_kwh: f64,
+    /// miles per gallon of petrol equivalent
+    pub mpge: f64,
+    pub litres_per_100km: f64,
+}
+
+/// Distance covered and fuel burnt by recent updates
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EfficiencyWindow {
+    /// time step, km and litres of each update
+    samples: VecDeque<(f64, f64, f64)>,
+    time: f64,
+}
+
+impl EfficiencyWindow {
+    pub(crate) fn record(&mut self, time_step: f64, km: f64, litres: f64, window: f64) {
+        self.samples.push_back((time_step, km, litres));
+        self.time += time_step;
+        while self.time > window {
+            let Some((time_step, ..)) = self.samples.pop_front() else {
+                break;
+            };
+            self.time -= time_step;
+        }
+    }
+
+    /// `None` when the car hasn't moved or burnt fuel within the window
+    pub(crate) fn efficiency(&self) -> Option<Efficiency> {
+        let (km, litres) =
+            self.samples.iter().fold((0.0, 0.0), |(km, litres), (_, d, l)| (km + d, litres + l));
+        if km <= 0.0 || litres <= 0.0 {
+            return None;
+        }
+        let kwh = litres * FUEL_ENERGY;
+
+        Some(Efficiency {
+            wh_per_km: kwh * 1000.0 / km,
+            km_per_kwh: km / kwh,
+            mpge: km / KM_PER_MILE / (kwh / KWH_PER_GALLON_EQUIVALENT),
+            litres_per_100km: litres * 100.0 / km,
+        })
+    }
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::{
+    Chime, ChimePriority, Disturbance, Fault, FreezeFrame, MaintenanceItem, PowerState,
+    StartInhibit,
+};
+
+/// Notable things that happened during an update, drained with [`Car::drain_events`](crate::Car::drain_events)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Event {
+    /// accelerator and brake started being pressed together
+    BothPedals {
+        accelerator: f64,
+        brake: f64,
+    },
+    PowerStateChanged {
+        from: PowerState,
+        to: PowerState,
+    },
+    /// air brake pressure (in bar) dropped below the warning threshold
+    LowAirPressure {
+        pressure: f64,
+    },
+    /// driver pulled away against the hand brake and it was released for them
+    HandBrakeAutoReleased,
+    /// electronic parking brake applied itself on a parked car
+    ParkingBrakeApplied,
+    /// rear brakes (in °C) are hot enough to fade, e.g. from driving with the hand brake on
+    BrakeOverheat {
+        temperature: f64,
+    },
+    /// automatic emergency braking took over, distance in m and speed in kmph
+    AebActivated {
+        obstacle_distance: f64,
+        speed: f64,
+    },
+    Disturbance(Disturbance),
+    /// driver has been at the wheel (in s) long enough to be drowsy, see [`Drowsiness`](crate::Drowsiness)
+    DrowsinessSuspected {
+        continuous_driving: f64,
+    },
+    /// simulator fell this many ticks behind real time and stepped over the elapsed time in one go
+    TicksMissed {
+        missed: u64,
+    },
+    /// critical fault put the car in limp-home mode, see [`Car::limp_mode`](crate::Car::limp_mode)
+    LimpModeEntered {
+        fault: Fault,
+        freeze_frame: FreezeFrame,
+    },
+    /// item wore past its service interval, see [`Car::service`](crate::Car::service)
+    ServiceDue {
+        item: MaintenanceItem,
+    },
+    Chime {
+        chime: Chime,
+        priority: ChimePriority,
+    },
+    /// state went NaN or infinite, e.g. from a zero in the config, and the car was reset to its
+    /// initial state
+    SimulationFault {
+        field: &'static str,
+    },
+    /// turning the key didn't start the car
+    StartInhibited {
+        reason: StartInhibit,
+    },
+    /// a running simulation was changed without restarting it, published on
+    /// `SimulatorHandle::events` even while paused
+    Reconfigured(Reconfiguration),
+}
+
+/// What changed in a live reconfiguration
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Reconfiguration {
+    Config,
+    Tick(Duration),
+    Driver,
+    RealTimeFactor(f64),
+}
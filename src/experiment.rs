@@ -0,0 +1,95 @@
+use std::io::{self, Write};
+
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{Car, CarConfig, InitialState};
+
+type Sampler<R> = Box<dyn FnMut(&mut R) -> CarConfig>;
+type RandomDriver<R> = Box<dyn FnMut(&mut Car, &mut R)>;
+
+/// Metrics of a single run of an [`Experiment`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct RunSummary {
+    pub run: usize,
+    /// in km
+    pub distance: f64,
+    /// share of the tank
+    pub fuel_used: f64,
+    /// in kmph
+    pub average_speed: f64,
+    pub max_speed: f64,
+}
+
+/// Runs the same scenario many times, drawing the config for each run from distributions,
+/// for sensitivity analysis
+pub struct Experiment<R> {
+    runs: usize,
+    updates: usize,
+    initial: InitialState,
+    sample: Sampler<R>,
+    driver: RandomDriver<R>,
+}
+
+impl<R: Rng> Experiment<R> {
+    /// Every run starts from `initial` and lasts `updates` updates
+    pub fn new(runs: usize, updates: usize, initial: InitialState) -> Self {
+        Self {
+            runs,
+            updates,
+            initial,
+            sample: Box::new(|_| CarConfig::default()),
+            driver: Box::new(|_, _| {}),
+        }
+    }
+
+    /// Draws the config of each run
+    pub fn config(mut self, sample: impl FnMut(&mut R) -> CarConfig + 'static) -> Self {
+        self.sample = Box::new(sample);
+        self
+    }
+
+    /// Sets the car's inputs before every update
+    pub fn driver(mut self, driver: impl FnMut(&mut Car, &mut R) + 'static) -> Self {
+        self.driver = Box::new(driver);
+        self
+    }
+
+    pub fn run(&mut self, rng: &mut R) -> Vec<RunSummary> {
+        (0..self.runs)
+            .map(|run| {
+                let config = (self.sample)(rng);
+                let mut car = Car::warm_start(self.initial.clone(), config);
+                let mut summary = RunSummary { run, ..Default::default() };
+                for _ in 0..self.updates {
+                    (self.driver)(&mut car, rng);
+                    car.update();
+                    summary.average_speed += car.speed() / self.updates as f64;
+                    summary.max_speed = summary.max_speed.max(car.speed());
+                }
+                summary.distance = car.distance();
+                summary.fuel_used = (self.initial.fuel_level - car.fuel_level()).max(0.0);
+
+                summary
+            })
+            .collect()
+    }
+}
+
+/// One row per run with a header, ready for loading into a data frame
+pub fn write_csv(summaries: &[RunSummary], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "run,distance,fuel_used,average_speed,max_speed")?;
+    for summary in summaries {
+        writeln!(
+            writer,
+            "{},{},{},{},{}",
+            summary.run,
+            summary.distance,
+            summary.fuel_used,
+            summary.average_speed,
+            summary.max_speed
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::Value;
+
+use crate::{Car, CarConfig, Gear, HandBrake, InitialState, Telemetry};
+
+/// Canonical drives whose telemetry is recorded as a golden trace, so changes to the physics
+/// show up as a diff instead of going unnoticed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scenario {
+    /// full throttle from rest, shifting up at 4000 rpm, for a minute
+    StandingStart,
+    /// full brake from 100 kmph in fifth until the car stops
+    PanicStop,
+}
+
+impl Scenario {
+    pub const ALL: [Scenario; 2] = [Scenario::StandingStart, Scenario::PanicStop];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Scenario::StandingStart => "standing_start",
+            Scenario::PanicStop => "panic_stop",
+        }
+    }
+
+    /// Telemetry after every update of the scenario, with the default config
+    pub fn run(self) -> Vec<Telemetry> {
+        let mut trace = vec![];
+        match self {
+            Scenario::StandingStart => {
+                let mut car = Car::new(1.0);
+                // Default config has no start inhibitors
+                _ = car.turn_key(true);
+                car.set_handbrake_position(HandBrake::Disengaged);
+                car.shift_gear(Gear::First);
+                car.set_accelerator_position(1.0);
+                for _ in 0..60 {
+                    if car.rpm() > 4000 {
+                        if let Some(gear) = car.gear().up() {
+                            car.shift_gear(gear);
+                        }
+                    }
+                    car.update();
+                    trace.push(car.telemetry());
+                }
+            }
+            Scenario::PanicStop => {
+                let initial = InitialState {
+                    speed: 100.0,
+                    gear: Gear::Fifth,
+                    hand_brake: HandBrake::Disengaged,
+                    running: true,
+                    ..Default::default()
+                };
+                let mut car = Car::warm_start(initial, CarConfig::default());
+                car.set_brake_position(1.0);
+                // Bounded in case a change stops the car from stopping
+                for _ in 0..120 {
+                    car.update();
+                    trace.push(car.telemetry());
+                    if car.speed() == 0.0 {
+                        break;
+                    }
+                }
+            }
+        }
+
+        trace
+    }
+
+    fn path(self, dir: &Path) -> PathBuf {
+        dir.join(self.name()).with_extension("json")
+    }
+
+    /// Compare a fresh run against the golden trace stored in `dir`
+    pub fn check(self, dir: &Path, tolerance: f64) -> Result<(), GoldenError> {
+        let golden: Vec<Telemetry> = serde_json::from_slice(&fs::read(self.path(dir))?)?;
+        compare(&self.run(), &golden, tolerance)?;
+
+        Ok(())
+    }
+
+    /// Record a fresh run as the golden trace in `dir`, after a deliberate change in behaviour
+    pub fn bless(self, dir: &Path) -> Result<(), GoldenError> {
+        let trace = serde_json::to_vec_pretty(&self.run())?;
+        fs::write(self.path(dir), trace)?;
+
+        Ok(())
+    }
+}
+
+/// First difference between a trace and its golden counterpart
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mismatch {
+    Length { expected: usize, actual: usize },
+    Field { update: usize, field: String, expected: Value, actual: Value },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Length { expected, actual } => {
+                write!(f, "expected {expected} updates, got {actual}")
+            }
+            Mismatch::Field { update, field, expected, actual } => {
+                write!(f, "{field} after update {update}: expected {expected}, got {actual}")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GoldenError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Mismatch(Mismatch),
+}
+
+impl fmt::Display for GoldenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoldenError::Io(e) => write!(f, "can't access golden trace: {e}"),
+            GoldenError::Json(e) => write!(f, "invalid golden trace: {e}"),
+            GoldenError::Mismatch(mismatch) => write!(f, "trace differs: {mismatch}"),
+        }
+    }
+}
+
+impl std::error::Error for GoldenError {}
+
+impl From<io::Error> for GoldenError {
+    fn from(e: io::Error) -> Self {
+        GoldenError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for GoldenError {
+    fn from(e: serde_json::Error) -> Self {
+        GoldenError::Json(e)
+    }
+}
+
+impl From<Mismatch> for GoldenError {
+    fn from(mismatch: Mismatch) -> Self {
+        GoldenError::Mismatch(mismatch)
+    }
+}
+
+/// Numeric fields, also inside nested objects and arrays, may differ by `tolerance` relative to
+/// the golden value (absolute below 1.0), everything else has to match exactly
+pub fn compare(actual: &[Telemetry], golden: &[Telemetry], tolerance: f64) -> Result<(), Mismatch> {
+    if actual.len() != golden.len() {
+        return Err(Mismatch::Length { expected: golden.len(), actual: actual.len() });
+    }
+
+    for (update, (actual, golden)) in actual.iter().zip(golden).enumerate() {
+        let (Ok(Value::Object(actual)), Ok(Value::Object(golden))) =
+            (serde_json::to_value(actual), serde_json::to_value(golden))
+        else {
+            continue;
+        };
+        let fields =
+            golden.keys().chain(actual.keys().filter(|field| !golden.contains_key(*field)));
+        for field in fields {
+            let expected = golden.get(field).unwrap_or(&Value::Null);
+            let actual = actual.get(field).cloned().unwrap_or(Value::Null);
+            if !matches(expected, &actual, tolerance) {
+                return Err(Mismatch::Field {
+                    update,
+                    field: field.clone(),
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn matches(expected: &Value, actual: &Value, tolerance: f64) -> bool {
+    match (expected, actual) {
+        (Value::Number(expected), Value::Number(actual)) => {
+            match (expected.as_f64(), actual.as_f64()) {
+                (Some(expected), Some(actual)) => {
+                    (actual - expected).abs() <= tolerance * expected.abs().max(1.0)
+                }
+                _ => expected == actual,
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            expected.len() == actual.len()
+                && expected.iter().zip(actual).all(|(e, a)| matches(e, a, tolerance))
+        }
+        (Value::Object(expected), Value::Object(actual)) => {
+            expected.len() == actual.len()
+                && expected
+                    .iter()
+                    .all(|(field, e)| actual.get(field).is_some_and(|a| matches(e, a, tolerance)))
+        }
+        _ => expected == actual,
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use tokio::sync::Mutex;
+
+use crate::{Car, Event, Telemetry};
+
+/// Shared access to one car from several tasks, e.g. a control task setting inputs, a telemetry
+/// task updating and reporting, and a scenario task injecting disturbances
+#[derive(Debug, Clone)]
+pub struct CarHandle {
+    car: Arc<Mutex<Car>>,
+}
+
+impl CarHandle {
+    pub fn new(car: Car) -> Self {
+        Self { car: Arc::new(Mutex::new(car)) }
+    }
+
+    /// Run `f` with exclusive access to the car, keep it short as other tasks wait on it
+    pub async fn with<T>(&self, f: impl FnOnce(&mut Car) -> T) -> T {
+        f(&mut *self.car.lock().await)
+    }
+
+    /// Update the car and return the resulting telemetry
+    pub async fn update(&self) -> Telemetry {
+        self.with(|car| {
+            car.update();
+            car.telemetry()
+        })
+        .await
+    }
+
+    pub async fn telemetry(&self) -> Telemetry {
+        self.with(|car| car.telemetry()).await
+    }
+
+    pub async fn drain_events(&self) -> Vec<Event> {
+        self.with(|car| car.drain_events().collect()).await
+    }
+
+    /// Car back out of the handle, `None` while other clones are alive
+    pub fn into_inner(self) -> Option<Car> {
+        Arc::into_inner(self.car).map(Mutex::into_inner)
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::defaults::AMBIENT_TEMPERATURE;
+
+/// °C per second the cabin warms up per kW of heat
+const CABIN_HEATING: f64 = 0.05;
+/// Share of the cabin's heat above ambient lost per second
+const CABIN_COOLING: f64 = 0.01;
+
+/// Electric cabin heater and defroster. The thermostat switches the element fully on below
+/// `setpoint - hysteresis` and off above `setpoint + hysteresis`, so it loads the alternator in
+/// bursts rather than drawing a constant current.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Heater {
+    /// drawn while the element is on, in kW
+    pub power: f64,
+    /// in °C
+    pub setpoint: f64,
+    /// in °C either side of the setpoint
+    pub hysteresis: f64,
+}
+
+impl Default for Heater {
+    fn default() -> Self {
+        Self { power: 3.0, setpoint: 24.0, hysteresis: 1.0 }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Cabin {
+    pub heater: Option<Heater>,
+    pub element_on: bool,
+    /// in °C
+    pub temperature: f64,
+}
+
+impl Default for Cabin {
+    fn default() -> Self {
+        Self { heater: None, element_on: false, temperature: AMBIENT_TEMPERATURE }
+    }
+}
+
+impl Cabin {
+    /// Electrical load of the heater in kW
+    pub fn load(&self) -> f64 {
+        match self.heater {
+            Some(heater) if self.element_on => heater.power,
+            _ => 0.0,
+        }
+    }
+
+    /// The element only runs while `powered`, i.e. with the engine running
+    pub fn update(&mut self, powered: bool, time_step: f64) {
+        self.element_on = match self.heater {
+            Some(heater) if powered => {
+                if self.temperature < heater.setpoint - heater.hysteresis {
+                    true
+                } else if self.temperature > heater.setpoint + heater.hysteresis {
+                    false
+                } else {
+                    self.element_on
+                }
+            }
+            _ => false,
+        };
+
+        let heating = CABIN_HEATING * self.load();
+        let cooling = (self.temperature - AMBIENT_TEMPERATURE) * CABIN_COOLING;
+        self.temperature += (heating - cooling) * time_step;
+    }
+}
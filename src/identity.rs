@@ -0,0 +1,95 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
+use serde::Serialize;
+
+// Transliteration of A-Z for the check digit, 0 marks letters VINs can't contain (I, O, Q)
+const LETTER_VALUES: [u32; 26] =
+    [1, 2, 3, 4, 5, 6, 7, 8, 0, 1, 2, 3, 4, 5, 0, 7, 0, 9, 2, 3, 4, 5, 6, 7, 8, 9];
+const WEIGHTS: [u32; 17] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+#[cfg(feature = "rand")]
+const VIN_CHARS: &[u8] = b"ABCDEFGHJKLMNPRSTUVWXYZ0123456789";
+// Model year codes for 2010 to 2030
+#[cfg(feature = "rand")]
+const YEAR_CODES: &[u8] = b"ABCDEFGHJKLMNPRSTVWXY";
+// World manufacturer identifiers with a few models each
+#[cfg(feature = "rand")]
+const MANUFACTURERS: &[(&str, &str, &[&str])] = &[
+    ("1HG", "Honda", &["Civic", "Accord"]),
+    ("WVW", "Volkswagen", &["Golf", "Polo"]),
+    ("JTD", "Toyota", &["Corolla", "Yaris"]),
+    ("WBA", "BMW", &["3 Series", "5 Series"]),
+    ("KMH", "Hyundai", &["i20", "Elantra"]),
+    ("MA3", "Maruti Suzuki", &["Swift", "Baleno"]),
+];
+
+/// Who the car is, attached to telemetry so downstream systems can tell vehicles apart
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Identity {
+    pub vin: String,
+    pub license_plate: String,
+    pub make: String,
+    pub model: String,
+    pub year: u16,
+}
+
+#[cfg(feature = "rand")]
+impl Identity {
+    /// Random but plausible identity, the VIN carries a valid check digit and the model year
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let (wmi, make, models) = MANUFACTURERS[rng.gen_range(0..MANUFACTURERS.len())];
+        let model = models[rng.gen_range(0..models.len())];
+        let year_index = rng.gen_range(0..YEAR_CODES.len());
+        let year = 2010 + year_index as u16;
+
+        let mut vin = wmi.to_owned();
+        // vehicle descriptor, check digit placeholder, model year, plant
+        (0..5).for_each(|_| vin.push(random_char(rng, VIN_CHARS)));
+        vin.push('0');
+        vin.push(YEAR_CODES[year_index] as char);
+        vin.push(random_char(rng, VIN_CHARS));
+        (0..6).for_each(|_| vin.push(random_char(rng, b"0123456789")));
+        let check_digit = vin_check_digit(&vin).expect("generated VIN is well formed");
+        vin.replace_range(8..9, &check_digit.to_string());
+
+        let mut license_plate = String::new();
+        (0..3).for_each(|_| license_plate.push(random_char(rng, b"ABCDEFGHJKLMNPRSTUVWXYZ")));
+        license_plate.push('-');
+        (0..4).for_each(|_| license_plate.push(random_char(rng, b"0123456789")));
+
+        Self { vin, license_plate, make: make.to_owned(), model: model.to_owned(), year }
+    }
+}
+
+#[cfg(feature = "rand")]
+fn random_char(rng: &mut impl Rng, chars: &[u8]) -> char {
+    chars[rng.gen_range(0..chars.len())] as char
+}
+
+/// Check digit (9th character) a VIN should have, `None` if it isn't 17 valid VIN characters
+pub fn vin_check_digit(vin: &str) -> Option<char> {
+    if vin.len() != 17 {
+        return None;
+    }
+
+    let mut sum = 0;
+    for (c, weight) in vin.chars().zip(WEIGHTS) {
+        let value = match c {
+            '0'..='9' => c.to_digit(10)?,
+            'A'..='Z' => match LETTER_VALUES[(c as u8 - b'A') as usize] {
+                0 => return None,
+                value => value,
+            },
+            _ => return None,
+        };
+        sum += value * weight;
+    }
+
+    match sum % 11 {
+        10 => Some('X'),
+        digit => char::from_digit(digit, 10),
+    }
+}
+
+pub fn is_valid_vin(vin: &str) -> bool {
+    vin_check_digit(vin).is_some_and(|digit| vin.chars().nth(8) == Some(digit))
+}
@@ -0,0 +1,60 @@
+use std::{io, time::Duration};
+
+use rdkafka::{
+    error::KafkaResult,
+    producer::{BaseProducer, BaseRecord, Producer},
+    ClientConfig,
+};
+
+use crate::{Encoding, Telemetry, TelemetrySink};
+
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Publishes every record to a topic, keyed by vehicle so a vehicle's telemetry stays in one partition
+pub struct KafkaSink {
+    producer: BaseProducer,
+    topic: String,
+    vehicle_id: String,
+    encoding: Encoding,
+}
+
+impl KafkaSink {
+    pub fn new(
+        brokers: &str,
+        topic: impl Into<String>,
+        vehicle_id: impl Into<String>,
+        encoding: Encoding,
+    ) -> KafkaResult<Self> {
+        let producer = ClientConfig::new().set("bootstrap.servers", brokers).create()?;
+
+        Ok(Self { producer, topic: topic.into(), vehicle_id: vehicle_id.into(), encoding })
+    }
+
+    /// For producer settings beyond the broker list
+    pub fn with_config(
+        config: &ClientConfig,
+        topic: impl Into<String>,
+        vehicle_id: impl Into<String>,
+        encoding: Encoding,
+    ) -> KafkaResult<Self> {
+        let producer = config.create()?;
+
+        Ok(Self { producer, topic: topic.into(), vehicle_id: vehicle_id.into(), encoding })
+    }
+}
+
+impl TelemetrySink for KafkaSink {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        let payload = self.encoding.encode(telemetry)?;
+        let record = BaseRecord::to(&self.topic).key(&self.vehicle_id).payload(&payload);
+        self.producer.send(record).map_err(|(e, _)| io::Error::other(e))?;
+        // Serve delivery callbacks so the queue doesn't fill up
+        self.producer.poll(Duration::ZERO);
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.producer.flush(FLUSH_TIMEOUT).map_err(io::Error::other)
+    }
+}
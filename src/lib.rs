@@ -1,19 +1,142 @@
-use std::f64::consts::PI;
-
-use serde::Serialize;
-
-const BASE_RPM: f64 = 750.0;
-const MAX_RPM: f64 = 5000.0;
-const WHEEL_RADIUS: f64 = 0.4; // in m
-const WHEEL_CIRCUMFERENCE: f64 = 2.0 * PI * WHEEL_RADIUS; // in m
-const SPEED_FACTOR: f64 = WHEEL_CIRCUMFERENCE * 0.006; // RPM to kmph formulation
-const SPEED_ALPHA: f64 = 0.5;
-const BRAKING_ALPHA: f64 = 0.5;
-const MAX_POWER: f64 = 100.0; // kW
-const MAX_TORQUE: f64 = 200.0; // Nm
-const BSFC: f64 = 180.0; // g/kWh
-
-#[derive(Debug, Default, PartialEq, Serialize)]
+use serde::{Deserialize, Serialize};
+
+use air_brake::AirBrake;
+use defaults::{
+    AEB_TIME_TO_COLLISION, AIR_DENSITY, AMBIENT_TEMPERATURE, BASE_RPM, BRAKE_COOLING,
+    BRAKE_DECELERATION, BRAKE_FADE, BRAKE_HEATING, BRAKE_OVERHEAT, DRIVE_AWAY_THROTTLE,
+    ENGINE_BRAKING, ENGINE_DROOP, ENGINE_VIBRATION, FRICTION_POWER, FUEL_DENSITY, GRAVITY,
+    LIMP_POWER, LIMP_SPEED, LOW_FUEL, MAX_BRAKE_SLIP, MAX_POWER, MAX_RPM, MAX_TRACTION_SLIP,
+    PARK_MAX_SPEED, ROAD_VIBRATION, SCALE_HEIGHT, SHOCK_DECAY,
+};
+use efficiency::EfficiencyWindow;
+use heater::Cabin;
+use limp::LimpMode;
+use math::{exponential_moving_average, smoothing_alpha};
+use odometer::Tampering;
+
+mod air_brake;
+#[cfg(feature = "rand")]
+mod anomaly;
+mod assertions;
+mod cadence;
+mod calibration;
+mod chime;
+mod coast_down;
+mod command;
+mod config;
+mod controller;
+mod controls;
+mod counters;
+mod dashboard;
+#[cfg(feature = "debug-state")]
+mod debug_state;
+pub mod defaults;
+#[cfg(feature = "rand")]
+mod distribution;
+mod drivers;
+#[cfg(feature = "rand")]
+mod drowsiness;
+mod dyno;
+mod efficiency;
+mod events;
+#[cfg(feature = "rand")]
+mod experiment;
+mod golden;
+#[cfg(feature = "simulator")]
+mod handle;
+mod heater;
+mod identity;
+#[cfg(feature = "kafka")]
+mod kafka;
+mod limp;
+mod maintenance;
+mod mapping;
+pub mod math;
+mod notifications;
+mod odometer;
+mod parking;
+mod power;
+#[cfg(feature = "protobuf")]
+pub mod proto;
+#[cfg(feature = "rand")]
+mod random_driver;
+mod random_events;
+mod rental;
+#[cfg(feature = "simulator")]
+mod simulator;
+mod sink;
+mod start_inhibit;
+mod surface;
+mod tachograph;
+mod telemetry;
+mod validator;
+mod wheel_speeds;
+
+pub use air_brake::AirBrakeConfig;
+#[cfg(feature = "rand")]
+pub use anomaly::{Anomalies, AnomalySink, InjectedAnomalies};
+pub use assertions::{AssertionFailure, Assertions};
+pub use cadence::{Cadence, SignalGroup};
+pub use calibration::{calibrate, trace_error, Parameter, TraceSample};
+pub use chime::{Chime, ChimePriority};
+pub use coast_down::{coast_down, CoastDown};
+pub use command::Command;
+pub use config::{
+    CarConfig, DriveMode, GearRatios, InitialState, InvalidFuelLevel, PedalMap, Tire, Units,
+    KM_PER_MILE,
+};
+#[cfg(feature = "simulator")]
+pub use controller::ActuatorLag;
+pub use controller::{Actuation, Controller};
+pub use controls::{ControlError, Controls};
+pub use counters::Counters;
+pub use dashboard::Dashboard;
+#[cfg(feature = "debug-state")]
+pub use debug_state::DebugState;
+#[cfg(feature = "rand")]
+pub use distribution::Distribution;
+pub use drivers::{DriverStats, Drivers};
+#[cfg(feature = "rand")]
+pub use drowsiness::Drowsiness;
+pub use dyno::{dyno, DynoPoint};
+pub use efficiency::Efficiency;
+pub use events::{Event, Reconfiguration};
+#[cfg(feature = "rand")]
+pub use experiment::{write_csv, Experiment, RunSummary};
+pub use golden::{compare, GoldenError, Mismatch, Scenario};
+#[cfg(feature = "simulator")]
+pub use handle::CarHandle;
+pub use heater::Heater;
+pub use identity::{is_valid_vin, vin_check_digit, Identity};
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+pub use limp::{Fault, FreezeFrame};
+pub use maintenance::{MaintenanceItem, MaintenanceState, WearCounter};
+pub use mapping::{FieldMapping, MappingError, TelemetryMapping};
+pub use notifications::{Notification, Notifications};
+pub use odometer::OdometerTamper;
+pub use parking::{BeepZone, ParkingSensor};
+pub use power::{PowerState, PowerTrigger};
+#[cfg(feature = "rand")]
+pub use random_driver::RandomDriver;
+pub use random_events::Disturbance;
+#[cfg(feature = "rand")]
+pub use random_events::RandomEvents;
+pub use rental::{RentalError, RentalSession, RentalState, TripSummary};
+#[cfg(feature = "simulator")]
+pub use simulator::{
+    Backpressure, Driver, SimStats, Simulator, SimulatorHandle, TelemetryStream, ZeroTick,
+};
+pub use sink::{DeltaSink, Encoding, FramedSink, TelemetrySink, WriterSink};
+pub use start_inhibit::{StartInhibit, StartInhibitors};
+pub use surface::Surface;
+pub use tachograph::Tachograph;
+pub use telemetry::{Limits, Telemetry, TELEMETRY_VERSION};
+pub use validator::{Validator, Violation};
+pub use wheel_speeds::WheelSpeeds;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Gear {
     #[default]
     Neutral,
@@ -23,9 +146,47 @@ pub enum Gear {
     Fourth,
     Fifth,
     Reverse,
+    /// Locks the driveline, automatic transmissions only
+    Park,
 }
 
-#[derive(Debug, Default, PartialEq, Serialize)]
+impl Gear {
+    /// Next gear up the gate, from park through reverse and neutral to fifth. `None` in fifth.
+    pub fn up(self) -> Option<Gear> {
+        match self {
+            Gear::Park => Some(Gear::Reverse),
+            Gear::Reverse => Some(Gear::Neutral),
+            Gear::Neutral => Some(Gear::First),
+            Gear::First => Some(Gear::Second),
+            Gear::Second => Some(Gear::Third),
+            Gear::Third => Some(Gear::Fourth),
+            Gear::Fourth => Some(Gear::Fifth),
+            Gear::Fifth => None,
+        }
+    }
+
+    /// Next gear down the gate, `None` in park
+    pub fn down(self) -> Option<Gear> {
+        match self {
+            Gear::Park => None,
+            Gear::Reverse => Some(Gear::Park),
+            Gear::Neutral => Some(Gear::Reverse),
+            Gear::First => Some(Gear::Neutral),
+            Gear::Second => Some(Gear::First),
+            Gear::Third => Some(Gear::Second),
+            Gear::Fourth => Some(Gear::Third),
+            Gear::Fifth => Some(Gear::Fourth),
+        }
+    }
+
+    /// `None` in neutral and park, see [`GearRatios::ratio`]
+    pub fn ratio(self, ratios: &GearRatios) -> Option<f64> {
+        ratios.ratio(&self)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum HandBrake {
     Disengaged,
     Half,
@@ -34,22 +195,51 @@ pub enum HandBrake {
 }
 
 impl HandBrake {
-    fn effect(&self) -> Option<f64> {
+    /// Lever travel at this detent, from 0.0 released to 1.0 fully pulled
+    pub fn travel(&self) -> f64 {
         match self {
-            HandBrake::Disengaged => None,
-            HandBrake::Half => Some(0.75),
-            HandBrake::Full => Some(1.0),
+            HandBrake::Disengaged => 0.0,
+            HandBrake::Half => 0.75,
+            HandBrake::Full => 1.0,
+        }
+    }
+
+    /// Detent view of a lever travel, anything pulled short of fully counts as half
+    pub fn from_travel(travel: f64) -> Self {
+        match travel {
+            travel if travel <= 0.0 => HandBrake::Disengaged,
+            travel if travel < 1.0 => HandBrake::Half,
+            _ => HandBrake::Full,
         }
     }
 }
 
+/// Speed lost in the last update (in kmph) and what took it away
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Deceleration {
+    /// service brake, hand brake and park pawl
+    pub brakes: f64,
+    /// engine dragged along by the wheels while off throttle in gear
+    pub engine: f64,
+    pub road: f64,
+}
+
 #[derive(Debug, Default)]
 pub struct Car {
     instantaneous_speeds: Vec<f64>,
     instantaneous_braking: Vec<f64>,
     /// effective value after brake has been applied
     effective_braking: f64,
+    /// braking effort before smoothing
+    raw_braking: f64,
     speed: f64,
+    /// model speed before smoothing
+    raw_speed: f64,
+    /// length of the current physics step in s, `config.time_step` or a sub-step of it
+    dt: f64,
+    /// driven in km, the odometer reads the same unless tampered with
+    distance: f64,
+    odometer_tamper: Option<Tampering>,
     engine_rpm: u32,
     transmission_rpm: f64,
     gear: Gear,
@@ -57,15 +247,359 @@ pub struct Car {
     brake_position: f64,
     clutch_position: f64,
     hand_brake: HandBrake,
+    /// lever travel, `hand_brake` is the detent view of it
+    hand_brake_travel: f64,
+    seatbelt_fastened: bool,
+    hood_open: bool,
+    /// in °C
+    rear_brake_temperature: f64,
     fuel_level: f64,
+    identity: Option<Identity>,
+    drive_mode: DriveMode,
+    deceleration: Deceleration,
+    power_state: PowerState,
+    /// trigger that will fire and seconds left till it does
+    power_transition: Option<(PowerTrigger, f64)>,
+    air_brake: AirBrake,
+    tachograph: Tachograph,
+    /// distance to an obstacle ahead in m, as seen by the AEB sensors
+    obstacle_distance: Option<f64>,
+    /// distance to an obstacle behind in m, as seen by the parking sensors
+    rear_obstacle_distance: Option<f64>,
+    aeb_active: bool,
+    /// speed gained in the last update was cut back to what the tires can grip
+    traction_limited: bool,
+    limp: Option<LimpMode>,
+    /// both pedals were pressed during the last update
+    pedal_overlap: bool,
+    /// in m/s² RMS
+    vibration: f64,
+    /// transient from potholes and debris, in m/s²
+    road_shock: f64,
+    surface: Surface,
+    /// above sea level in m
+    altitude: f64,
+    /// rise over run, negative downhill
+    gradient: f64,
+    /// in kW, what the engine put through the wheels in the last step
+    wheel_power: f64,
+    sirens: bool,
+    cabin: Cabin,
+    maintenance: MaintenanceState,
+    drivers: Drivers,
+    efficiency: EfficiencyWindow,
+    counters: Counters,
+    /// the car moved since the key was last turned on
+    moved_this_cycle: bool,
+    /// sounding since their condition started
+    chimes: Vec<Chime>,
+    events: Vec<Event>,
+    config: CarConfig,
+    initial: InitialState,
+}
+
+impl TryFrom<InitialState> for Car {
+    type Error = InvalidFuelLevel;
+
+    /// Car in a validated initial state with the default config
+    fn try_from(initial: InitialState) -> Result<Self, Self::Error> {
+        initial.validate()?;
+
+        Ok(Self::warm_start(initial, CarConfig::default()))
+    }
 }
 
 impl Car {
+    /// `fuel_level` is a share of the tank and isn't checked, see [`Car::try_new`].
+    ///
+    /// The car starts asleep with the engine off and revs at 0 rpm until [`Car::turn_key`]
+    /// cranks it. Before power states it ran as soon as it had fuel. Use [`Car::warm_start`]
+    /// with `running: true` for a car whose engine is already running.
     pub fn new(fuel_level: f64) -> Self {
-        Self { fuel_level, ..Default::default() }
+        Self::with_config(fuel_level, CarConfig::default())
+    }
+
+    /// Like [`Car::new`], but rejects a fuel level outside 0.0..=1.0
+    pub fn try_new(fuel_level: f64) -> Result<Self, InvalidFuelLevel> {
+        Self::try_from(InitialState { fuel_level, ..Default::default() })
+    }
+
+    pub fn with_config(fuel_level: f64, config: CarConfig) -> Self {
+        Self::warm_start(InitialState { fuel_level, ..Default::default() }, config)
+    }
+
+    /// Car already in the given state, e.g. moving. Pedals start released, so set the
+    /// accelerator before the first update to hold speed.
+    pub fn warm_start(initial: InitialState, config: CarConfig) -> Self {
+        let mut car = Self {
+            instantaneous_speeds: vec![initial.speed],
+            speed: initial.speed,
+            raw_speed: initial.speed,
+            gear: initial.gear,
+            hand_brake: initial.hand_brake.clone(),
+            hand_brake_travel: initial.hand_brake.travel(),
+            rear_brake_temperature: AMBIENT_TEMPERATURE,
+            fuel_level: initial.fuel_level,
+            config,
+            ..Default::default()
+        };
+        if initial.running {
+            car.power_state = PowerState::Ready;
+            car.counters.key_cycles = 1;
+            // Compressor had time to fill the tanks
+            if let Some(air_brakes) = &car.config.air_brakes {
+                car.air_brake = AirBrake::new(air_brakes.cut_out);
+            }
+        }
+        car.initial = initial;
+
+        car
+    }
+
+    /// Back to the initial state the car was built with, keeping its config, drive mode, identity
+    /// and usage counters
+    pub fn reset(&mut self) {
+        let mut car = Self::warm_start(self.initial.clone(), self.config.clone());
+        car.drive_mode = self.drive_mode;
+        car.counters = self.counters;
+        car.identity = self.identity.take();
+        car.drivers = self.drivers.take_keys();
+        *self = car;
+    }
+
+    pub fn set_identity(&mut self, identity: Identity) {
+        self.identity = Some(identity);
+    }
+
+    pub fn identity(&self) -> Option<&Identity> {
+        self.identity.as_ref()
+    }
+
+    /// Bind a key fob to a driver profile
+    pub fn register_key(&mut self, key: u32, driver: impl Into<String>) {
+        self.drivers.register(key, driver.into());
+    }
+
+    /// Driver picks up a key, returns false if the key isn't bound to a profile
+    pub fn use_key(&mut self, key: u32) -> bool {
+        self.drivers.select(key)
+    }
+
+    pub fn drivers(&self) -> &Drivers {
+        &self.drivers
+    }
+
+    /// Printable dashboard, `Car`'s own `Display` with more options
+    pub fn dashboard(&self) -> Dashboard<'_> {
+        Dashboard::new(self)
+    }
+
+    pub fn config(&self) -> &CarConfig {
+        &self.config
+    }
+
+    /// Swap the config of a car mid-drive, its state carries over and [`Car::reset`] keeps the
+    /// new config
+    pub fn set_config(&mut self, config: CarConfig) {
+        self.config = config;
+        self.emit(Event::Reconfigured(Reconfiguration::Config));
+    }
+
+    /// Eco and Sport also bring their own pedal map, Normal goes back to `config.pedal_map`
+    pub fn set_drive_mode(&mut self, mode: DriveMode) {
+        self.drive_mode = mode;
+    }
+
+    pub fn drive_mode(&self) -> DriveMode {
+        self.drive_mode
+    }
+
+    /// Key on starts the engine after cranking, unless one of `config.start_inhibitors` refuses
+    /// to. Key off drops to accessory mode and eventually sleep.
+    pub fn turn_key(&mut self, on: bool) -> Result<(), StartInhibit> {
+        if on {
+            if let Some(reason) = self.start_inhibit() {
+                self.emit(Event::StartInhibited { reason });
+                return Err(reason);
+            }
+            if self.trigger_power(PowerTrigger::KeyOn) {
+                self.counters.key_cycles += 1;
+                self.moved_this_cycle = false;
+            }
+            // Crank, also restarts after a stall with the key still on
+            if self.power_state == PowerState::IgnitionOn {
+                self.power_transition = Some((PowerTrigger::Cranked, power::CRANK_TIME));
+            }
+        } else if self.trigger_power(PowerTrigger::KeyOff) {
+            self.power_transition = Some((PowerTrigger::SleepTimeout, power::SLEEP_TIMEOUT));
+            if self.limp.is_some_and(|limp| limp.cleared) {
+                self.limp = None;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start_inhibit(&self) -> Option<StartInhibit> {
+        let inhibitors = self.config.start_inhibitors;
+        if inhibitors.brake_to_start && self.brake_position <= 0.0 {
+            Some(StartInhibit::BrakeNotPressed)
+        } else if inhibitors.immobilizer && self.drivers.active().is_none() {
+            Some(StartInhibit::Immobilized)
+        } else if inhibitors.hood_closed && self.hood_open {
+            Some(StartInhibit::HoodOpen)
+        } else {
+            None
+        }
+    }
+
+    /// Fault that put the car in limp-home mode, power and speed are limited until the fault is
+    /// cleared and the ignition cycled
+    pub fn limp_mode(&self) -> Option<Fault> {
+        self.limp.map(|limp| limp.fault)
+    }
+
+    /// Signals captured when the car went into limp-home mode
+    pub fn freeze_frame(&self) -> Option<&FreezeFrame> {
+        self.limp.as_ref().map(|limp| &limp.freeze_frame)
+    }
+
+    pub fn clear_fault(&mut self) {
+        if let Some(limp) = &mut self.limp {
+            limp.cleared = true;
+        }
+    }
+
+    fn enter_limp_mode(&mut self, fault: Fault) {
+        if self.limp.is_none() {
+            let freeze_frame = FreezeFrame {
+                speed: self.speed,
+                rpm: self.engine_rpm,
+                fuel_level: self.fuel_level,
+                rear_brake_temperature: self.rear_brake_temperature,
+                air_pressure: self.air_pressure(),
+                accelerator_position: self.accelerator_position,
+                brake_position: self.brake_position,
+                clutch_position: self.clutch_position,
+            };
+            self.limp = Some(LimpMode { fault, freeze_frame, cleared: false });
+            self.emit(Event::LimpModeEntered { fault, freeze_frame });
+        }
+    }
+
+    /// Wake the car from sleep on an external command, it goes back to sleep if the key isn't turned
+    pub fn wake(&mut self) {
+        if self.power_state == PowerState::Sleep && self.power_transition.is_none() {
+            self.power_transition = Some((PowerTrigger::Wake, power::WAKE_TIME));
+        }
+    }
+
+    pub fn power_state(&self) -> PowerState {
+        self.power_state
+    }
+
+    pub fn counters(&self) -> &Counters {
+        &self.counters
+    }
+
+    fn update_counters(&mut self) {
+        if self.power_state == PowerState::Ready {
+            self.counters.engine_hours += self.dt / 3600.0;
+        }
+        if self.speed != 0.0 && !self.moved_this_cycle {
+            self.moved_this_cycle = true;
+            self.counters.drive_sessions += 1;
+        }
+    }
+
+    /// Moves along the transition table, returns false if the trigger doesn't apply
+    fn trigger_power(&mut self, trigger: PowerTrigger) -> bool {
+        let Some(state) = self.power_state.next(trigger) else {
+            return false;
+        };
+        self.power_transition = None;
+        self.emit(Event::PowerStateChanged { from: self.power_state, to: state });
+        self.power_state = state;
+
+        true
+    }
+
+    fn update_power_state(&mut self, time_step: f64) {
+        // Engine stalls when running out of fuel
+        if self.power_state == PowerState::Ready && self.fuel_level <= 0.0 {
+            self.trigger_power(PowerTrigger::Stall);
+            return;
+        }
+
+        let Some((trigger, remaining)) = self.power_transition else {
+            return;
+        };
+        // Fires on the update that brings it closest to due
+        let remaining = remaining - time_step;
+        if remaining > time_step / 2.0 {
+            self.power_transition = Some((trigger, remaining));
+            return;
+        }
+        self.power_transition = None;
+        // Can't crank without fuel
+        if trigger == PowerTrigger::Cranked && self.fuel_level <= 0.0 {
+            return;
+        }
+        self.trigger_power(trigger);
+        if self.power_state == PowerState::Accessory {
+            self.power_transition = Some((PowerTrigger::SleepTimeout, power::SLEEP_TIMEOUT));
+        }
+    }
+
+    pub(crate) fn emit(&mut self, event: Event) {
+        #[cfg(feature = "tracing")]
+        tracing::info!(?event, "car event");
+        self.events.push(event);
+    }
+
+    /// Events raised since the last call
+    pub fn drain_events(&mut self) -> impl Iterator<Item = Event> + '_ {
+        self.events.drain(..)
+    }
+
+    /// Same as calling the matching setter
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::SetAccelerator(position) => self.set_accelerator_position(position),
+            Command::SetBrake(position) => self.set_brake_position(position),
+            Command::SetClutch(position) => self.set_clutch_position(position),
+            Command::ShiftGear(gear) => self.shift_gear(gear),
+            Command::SetHandBrake(position) => self.set_handbrake_position(position),
+            // A refused start is raised as an event
+            Command::TurnKey(on) => _ = self.turn_key(on),
+            Command::Wake => self.wake(),
+            Command::SetSeatbelt(fastened) => self.set_seatbelt(fastened),
+            Command::SetDriveMode(mode) => self.set_drive_mode(mode),
+            Command::Refuel(fuel_level) => self.refuel(fuel_level),
+            Command::ClearFault => self.clear_fault(),
+            Command::SetSirens(on) => self.set_sirens(on),
+            Command::SetObstacleDistance(distance) => self.set_obstacle_distance(distance),
+            Command::SetSurface(surface) => self.set_surface(surface),
+            Command::SetAltitude(altitude) => self.set_altitude(altitude),
+            Command::SetGradient(gradient) => self.set_gradient(gradient),
+            Command::SetHeater(heater) => self.set_heater(heater),
+        }
+    }
+
+    /// Set several inputs at once, e.g. `car.controls().accelerator(0.4).brake(0.0).apply()`
+    pub fn controls(&mut self) -> Controls<'_> {
+        Controls::new(self)
     }
 
+    /// Park is refused above walking speed, the car stays in its current gear
     pub fn shift_gear(&mut self, gear: Gear) {
+        if gear == Gear::Park && self.speed.abs() > PARK_MAX_SPEED {
+            return;
+        }
+        #[cfg(feature = "tracing")]
+        if gear != self.gear {
+            tracing::debug!(from = ?self.gear, to = ?gear, "gear shift");
+        }
         self.gear = gear;
     }
 
@@ -75,7 +609,9 @@ impl Car {
 
     pub fn set_accelerator_position(&mut self, position: f64) {
         self.accelerator_position = position;
-        self.brake_position = 0.0;
+        if !self.config.dual_pedal {
+            self.brake_position = 0.0;
+        }
     }
 
     pub fn accelerator_position(&self) -> f64 {
@@ -93,8 +629,10 @@ impl Car {
     pub fn smooth_braking(&mut self) -> f64 {
         self.instantaneous_braking.reverse();
         self.instantaneous_braking.resize_with(2, || 0.0);
-        self.instantaneous_braking =
-            exponential_moving_average(&self.instantaneous_braking, BRAKING_ALPHA);
+        self.instantaneous_braking = exponential_moving_average(
+            &self.instantaneous_braking,
+            smoothing_alpha(self.config.braking_smoothing, self.dt),
+        );
         self.instantaneous_braking.reverse();
 
         self.instantaneous_braking[0]
@@ -102,20 +640,157 @@ impl Car {
 
     pub fn set_brake_position(&mut self, position: f64) {
         self.brake_position = position;
-        self.accelerator_position = 0.0;
+        if !self.config.dual_pedal {
+            self.accelerator_position = 0.0;
+        }
+    }
+
+    /// Eco and Sport bring their own map, Normal keeps the one in the config
+    fn pedal_map(&self) -> PedalMap {
+        match self.drive_mode {
+            DriveMode::Normal => self.config.pedal_map,
+            mode => mode.pedal_map(),
+        }
+    }
+
+    /// Engine demand from the accelerator, brake overrides throttle when both are pressed
+    fn throttle(&self) -> f64 {
+        if self.brake_position > 0.0 || self.aeb_active {
+            return 0.0;
+        }
+        let demand = match self.config.kickdown {
+            Some(detent) if self.accelerator_position >= detent => 1.0,
+            _ => self.pedal_map().demand(self.accelerator_position),
+        };
+
+        let demand = if self.limp.is_some() { demand.min(LIMP_POWER) } else { demand };
+
+        // Naturally aspirated, the engine breathes thinner air up high
+        demand * self.air_density() * self.drive_mode.max_power() / MAX_POWER
+    }
+
+    /// Virtual obstacle ahead for AEB, `None` when the road is clear. The obstacle stays put,
+    /// so the distance shrinks as the car drives towards it.
+    pub fn set_obstacle_distance(&mut self, distance: Option<f64>) {
+        self.obstacle_distance = distance;
+    }
+
+    pub fn obstacle_distance(&self) -> Option<f64> {
+        self.obstacle_distance
+    }
+
+    /// Virtual obstacle behind for the parking sensors, `None` when there's nothing there.
+    /// Like the one ahead, it stays put as the car moves.
+    pub fn set_rear_obstacle_distance(&mut self, distance: Option<f64>) {
+        self.rear_obstacle_distance = distance;
+    }
+
+    pub fn rear_obstacle_distance(&self) -> Option<f64> {
+        self.rear_obstacle_distance
+    }
+
+    /// Parking sensor reading, only while in reverse with an obstacle in range
+    pub fn parking_sensor(&self) -> Option<ParkingSensor> {
+        if self.gear != Gear::Reverse {
+            return None;
+        }
+
+        self.rear_obstacle_distance.and_then(ParkingSensor::read)
+    }
+
+    /// AEB is overriding the driver with full braking
+    pub fn aeb_active(&self) -> bool {
+        self.aeb_active
+    }
+
+    /// Lit whenever braking is demanded, by the driver or by AEB
+    pub fn brake_light(&self) -> bool {
+        self.brake_position > 0.0 || self.aeb_active
+    }
+
+    pub fn reverse_light(&self) -> bool {
+        self.gear == Gear::Reverse
+    }
+
+    /// Ready-to-drive lamp on the cluster, lit while the engine runs
+    pub fn ready_lamp(&self) -> bool {
+        self.power_state == PowerState::Ready
+    }
+
+    fn update_aeb(&mut self) {
+        let speed = self.speed / 3.6; // m/s
+        let collision_imminent = match self.obstacle_distance {
+            Some(distance) if self.config.aeb && speed > 0.0 => {
+                distance / speed < AEB_TIME_TO_COLLISION
+            }
+            _ => false,
+        };
+        if collision_imminent && !self.aeb_active {
+            self.emit(Event::AebActivated {
+                obstacle_distance: self.obstacle_distance.unwrap_or_default(),
+                speed: self.speed,
+            });
+        }
+        self.aeb_active = collision_imminent;
+    }
+
+    /// Chimes whose condition currently holds
+    pub fn chimes(&self) -> &[Chime] {
+        &self.chimes
+    }
+
+    fn update_chimes(&mut self) {
+        let moving = self.speed != 0.0;
+        for chime in Chime::ALL {
+            let sounding = match chime {
+                Chime::Seatbelt => moving && !self.seatbelt_fastened,
+                Chime::LowFuel => self.fuel_level < LOW_FUEL,
+                Chime::HandBrakeOn => moving && self.hand_brake != HandBrake::Disengaged,
+            };
+            let was_sounding = self.chimes.contains(&chime);
+            if sounding && !was_sounding {
+                self.chimes.push(chime);
+                self.emit(Event::Chime { chime, priority: chime.priority() });
+            } else if !sounding && was_sounding {
+                self.chimes.retain(|active| *active != chime);
+            }
+        }
+    }
+
+    fn update_pedal_overlap(&mut self) {
+        let overlap = self.accelerator_position > 0.0 && self.brake_position > 0.0;
+        if overlap && !self.pedal_overlap {
+            self.emit(Event::BothPedals {
+                accelerator: self.accelerator_position,
+                brake: self.brake_position,
+            });
+        }
+        self.pedal_overlap = overlap;
     }
 
     pub fn update_braking(&mut self) {
-        let mut braking = self.brake_position;
+        let brake_position = if self.aeb_active { 1.0 } else { self.brake_position };
+        let mut braking = brake_position * self.config.brake_gain;
+
+        if let Some(air_brakes) = self.config.air_brakes.clone() {
+            let running = self.power_state == PowerState::Ready;
+            let time_step = self.dt;
+            if self.air_brake.update(&air_brakes, brake_position, running, time_step) {
+                self.emit(Event::LowAirPressure { pressure: self.air_brake.pressure });
+                self.enter_limp_mode(Fault::LowAirPressure);
+            }
+            braking *= self.air_brake.effectiveness(&air_brakes);
+        }
 
         // Take into account effect of handbrake
-        if let Some(effect) = self.hand_brake.effect() {
-            braking = effect.max(braking)
+        if let Some(effect) = self.hand_brake_effect() {
+            braking = (effect * self.hand_brake_fade()).max(braking)
         }
 
         if braking > 0.1 {
             self.instantaneous_braking.push(braking);
         }
+        self.raw_braking = braking;
 
         self.effective_braking = if braking > 0.0 {
             self.smooth_braking()
@@ -129,48 +804,171 @@ impl Car {
         self.brake_position
     }
 
+    /// Braking effort from the brakes and hand brake, before smoothing
+    pub fn braking_raw(&self) -> f64 {
+        self.raw_braking
+    }
+
+    /// Braking effort as applied to the car
+    pub fn braking_filtered(&self) -> f64 {
+        self.effective_braking
+    }
+
     pub fn set_handbrake_position(&mut self, position: HandBrake) {
-        self.hand_brake = position;
+        self.set_hand_brake_travel(position.travel());
     }
 
     pub fn hand_brake(&self) -> &HandBrake {
         &self.hand_brake
     }
 
-    fn transmission_ratio(&self) -> f64 {
-        match self.gear {
-            Gear::Reverse => -0.10,
-            Gear::Neutral => 0.0,
-            Gear::First => 0.30,
-            Gear::Second => 0.50,
-            Gear::Third => 0.80,
-            Gear::Fourth => 1.0,
-            Gear::Fifth => 1.40,
+    /// Pull the lever anywhere between released (0.0) and fully pulled (1.0), e.g. for gradual
+    /// application by an electric parking brake
+    pub fn set_hand_brake_travel(&mut self, travel: f64) {
+        self.hand_brake_travel = travel.clamp(0.0, 1.0);
+        self.hand_brake = HandBrake::from_travel(self.hand_brake_travel);
+    }
+
+    pub fn hand_brake_travel(&self) -> f64 {
+        self.hand_brake_travel
+    }
+
+    pub fn set_seatbelt(&mut self, fastened: bool) {
+        self.seatbelt_fastened = fastened;
+    }
+
+    pub fn seatbelt_fastened(&self) -> bool {
+        self.seatbelt_fastened
+    }
+
+    pub fn set_hood_open(&mut self, open: bool) {
+        self.hood_open = open;
+    }
+
+    pub fn hood_open(&self) -> bool {
+        self.hood_open
+    }
+
+    // Braking the hand brake applies before fade, `None` when released
+    fn hand_brake_effect(&self) -> Option<f64> {
+        (self.hand_brake_travel > 0.0).then_some(self.hand_brake_travel)
+    }
+
+    /// in °C
+    pub fn rear_brake_temperature(&self) -> f64 {
+        self.rear_brake_temperature
+    }
+
+    // Share of its holding force the hand brake keeps at the current rear brake temperature
+    fn hand_brake_fade(&self) -> f64 {
+        let (start, end) = BRAKE_FADE;
+        1.0 - 0.7 * ((self.rear_brake_temperature - start) / (end - start)).clamp(0.0, 1.0)
+    }
+
+    fn update_hand_brake(&mut self) {
+        let throttle = self.throttle();
+        let pulling = self.power_state == PowerState::Ready
+            && self.drivetrain_engaged()
+            && self.hand_brake_effect().is_some();
+        let auto_release = self.config.hand_brake_auto_release
+            || self.config.electronic_parking_brake && self.seatbelt_fastened;
+        if pulling && throttle > DRIVE_AWAY_THROTTLE && auto_release {
+            self.set_handbrake_position(HandBrake::Disengaged);
+            self.emit(Event::HandBrakeAutoReleased);
+        }
+
+        let parked = self.speed == 0.0
+            && matches!(self.gear, Gear::Neutral | Gear::Park)
+            && matches!(self.power_state, PowerState::Sleep | PowerState::Accessory);
+        if self.config.electronic_parking_brake && parked && self.hand_brake != HandBrake::Full {
+            self.set_handbrake_position(HandBrake::Full);
+            self.emit(Event::ParkingBrakeApplied);
         }
+
+        let was_overheated = self.rear_brake_temperature >= BRAKE_OVERHEAT;
+        let time_step = self.dt;
+        let heating = match self.hand_brake_effect() {
+            Some(effect) if pulling => BRAKE_HEATING * effect * throttle,
+            _ => 0.0,
+        };
+        let cooling = (self.rear_brake_temperature - AMBIENT_TEMPERATURE)
+            * BRAKE_COOLING
+            * self.air_density();
+        self.rear_brake_temperature += (heating - cooling) * time_step;
+        if !was_overheated && self.rear_brake_temperature >= BRAKE_OVERHEAT {
+            self.emit(Event::BrakeOverheat { temperature: self.rear_brake_temperature });
+            self.enter_limp_mode(Fault::BrakeOverheat);
+        }
+    }
+
+    fn transmission_ratio(&self) -> Option<f64> {
+        self.config.gear_ratios.ratio(&self.gear)
     }
 
     fn update_rpm(&mut self) {
-        let rpm = if self.fuel_level > 0.0 {
-            BASE_RPM + (MAX_RPM - BASE_RPM) * self.accelerator_position
+        let rpm = if self.fuel_level > 0.0 && self.power_state == PowerState::Ready {
+            // The pedal sets engine speed, which sags as the road loads the engine
+            let load = if self.drivetrain_engaged() {
+                (self.road_power() / (self.drive_mode.max_power() * self.air_density())).min(1.0)
+            } else {
+                0.0
+            };
+            (BASE_RPM + (MAX_RPM - BASE_RPM) * self.throttle()) * (1.0 - ENGINE_DROOP * load)
         } else {
             0.0
         };
         self.engine_rpm = rpm as u32;
-        self.transmission_rpm = if self.clutch_position <= 0.5 {
-            rpm * self.transmission_ratio() // above biting point
-        } else {
-            0.0 // Transmission is disconnected
+        self.transmission_rpm = match self.transmission_ratio() {
+            // above biting point, transmission spins backwards in reverse
+            Some(ratio) if self.drivetrain_engaged() => {
+                let direction = if self.gear == Gear::Reverse { -1.0 } else { 1.0 };
+                direction * rpm / ratio
+            }
+            _ => 0.0, // Transmission is disconnected
         };
     }
 
+    /// Engine is connected to the wheels, i.e. in gear with the clutch above biting point
+    fn drivetrain_engaged(&self) -> bool {
+        self.clutch_position <= 0.5 && !matches!(self.gear, Gear::Neutral | Gear::Park)
+    }
+
     pub fn rpm(&self) -> u32 {
         self.engine_rpm
     }
 
+    /// Wheel speed sensors in kmph, see [`WheelSpeeds`]
+    pub fn wheel_speeds(&self) -> WheelSpeeds {
+        let direction = self.speed.signum();
+        // Share of the grip the service brakes ask for, past all of it the wheels lock
+        let braking = self.effective_braking * BRAKE_DECELERATION / (self.surface.grip() * GRAVITY);
+        let braked =
+            if braking < 1.0 { self.speed * (1.0 - MAX_BRAKE_SLIP * braking) } else { 0.0 };
+        let front = if self.throttle() > 0.0 && self.drivetrain_engaged() {
+            // Driveline pushes the driven wheels ahead, as far as the tires give
+            let lead = (self.wheel_speed() - self.speed) * direction;
+            self.speed + lead.clamp(0.0, MAX_TRACTION_SLIP * self.speed.abs()) * direction
+        } else {
+            braked
+        };
+        let rear = braked * (1.0 - self.hand_brake_travel);
+
+        WheelSpeeds { front_left: front, front_right: front, rear_left: rear, rear_right: rear }
+    }
+
+    /// Speed in kmph the wheels would turn at with the current transmission RPM
+    fn wheel_speed(&self) -> f64 {
+        let wheel_rpm = self.transmission_rpm / self.config.final_drive;
+        wheel_rpm * self.config.tire.circumference() * 60.0 / 1000.0
+    }
+
     fn smooth_speed(&mut self) -> f64 {
         let initial_speed = self.instantaneous_speeds[0];
 
-        let speeds = exponential_moving_average(&self.instantaneous_speeds, SPEED_ALPHA);
+        let speeds = exponential_moving_average(
+            &self.instantaneous_speeds,
+            smoothing_alpha(self.config.speed_smoothing, self.dt),
+        );
         let speed = speeds.last().unwrap();
 
         // To ensure we are working with only a small window of values. Here that is 2 values,
@@ -181,33 +979,360 @@ impl Car {
         *speed
     }
 
+    /// Speed lost this update to the engine when coasting off throttle in a forward gear,
+    /// lower gears spin the engine faster and so drag harder
+    fn engine_drag(&self) -> f64 {
+        match self.transmission_ratio() {
+            Some(ratio)
+                if self.throttle() == 0.0
+                    && self.drivetrain_engaged()
+                    && self.gear != Gear::Reverse
+                    && self.speed > self.wheel_speed() =>
+            {
+                ENGINE_BRAKING * ratio * self.dt
+            }
+            _ => 0.0,
+        }
+    }
+
     fn update_speed(&mut self) {
-        // Don't change speed much if clutch engaged
-        if self.clutch_position > 0.5 {
-            self.speed *= 0.97 - self.effective_braking; // decrease speed by a small factor(0.03) anyways to emulate road resistence
+        let initial_speed = self.speed;
+        self.wheel_power = 0.0;
+        // Park pawl locks the driveline
+        if self.gear == Gear::Park {
+            self.speed = 0.0;
+            self.raw_speed = 0.0;
+            self.instantaneous_speeds = vec![0.0];
+            self.deceleration = Deceleration { brakes: initial_speed, ..Default::default() };
             return;
         }
-        self.speed = if self.accelerator_position == 0.0
-            && (self.speed < 3.0 || self.effective_braking > 0.75)
-        {
-            0.0
-        } else {
-            let speed = self.transmission_rpm * SPEED_FACTOR * (1.0 - self.effective_braking);
+        // Coast when the engine is decoupled, in neutral or with the clutch pressed, revving won't change speed
+        if !self.drivetrain_engaged() {
+            // Slow down from rolling resistance, drag, the slope and the brakes, down to a stop.
+            // Rolling downhill picks up speed instead.
+            let road = self.road_deceleration();
+            let brakes = self.effective_braking * BRAKE_DECELERATION;
+            let lost = ((road + brakes) * self.dt * 3.6).min(self.speed.abs());
+            self.speed -= lost * self.speed.signum();
+            // Restart smoothing from the coasting speed once a gear is engaged again
+            self.instantaneous_speeds = vec![self.speed];
+            self.raw_speed = self.speed;
+            let share = if brakes > 0.0 { road.max(0.0) / (road.max(0.0) + brakes) } else { 1.0 };
+            self.deceleration =
+                Deceleration { brakes: lost * (1.0 - share), engine: 0.0, road: lost * share };
+            return;
+        }
+        let engine_drag = self.engine_drag();
+        self.speed =
+            if self.throttle() == 0.0 && (self.speed < 3.0 || self.effective_braking > 0.75) {
+                self.deceleration = if self.effective_braking > 0.0 {
+                    Deceleration { brakes: initial_speed, ..Default::default() }
+                } else {
+                    Deceleration { road: initial_speed, ..Default::default() }
+                };
+                self.raw_speed = 0.0;
+                0.0
+            } else if engine_drag > 0.0 {
+                // Engine can't be dragged below idle, so we don't slow down past the gear's idle speed
+                let slope = self.slope_deceleration() * self.dt * 3.6;
+                let coasting_speed = (self.speed - engine_drag - slope).max(self.wheel_speed());
+                let speed = coasting_speed - self.brake_loss(coasting_speed);
+                self.deceleration = Deceleration {
+                    brakes: coasting_speed - speed,
+                    engine: initial_speed - coasting_speed,
+                    road: 0.0,
+                };
+                self.instantaneous_speeds = vec![speed];
+                self.raw_speed = speed;
+                speed
+            } else {
+                let wheel_speed = self.wheel_speed();
+                self.instantaneous_speeds.push(wheel_speed);
+                self.raw_speed = wheel_speed;
+                let driven_speed = self.smooth_speed();
 
-            self.instantaneous_speeds.push(speed);
-            self.smooth_speed()
-        };
+                // Brakes fight the engine, the next update is smoothed from the braked speed
+                let brakes = self.brake_loss(driven_speed);
+                let speed = driven_speed - brakes * driven_speed.signum();
+                self.instantaneous_speeds[0] = speed;
+                self.deceleration = Deceleration { brakes, ..Default::default() };
+
+                let speed = self.limit_power(initial_speed, speed);
+                let speed = self.limit_traction(initial_speed, speed);
+                if self.throttle() > 0.0 {
+                    let acceleration = (speed - initial_speed) / 3.6 / self.dt;
+                    let force = self.config.mass * (acceleration + self.road_deceleration());
+                    self.wheel_power = (force * speed.abs() / 3.6 / 1000.0).max(0.0);
+                }
+
+                speed
+            };
+        let speed_cap = self.speed_cap();
+        self.speed = self.speed.min(speed_cap);
+        self.raw_speed = self.raw_speed.min(speed_cap);
+    }
+
+    // Speed (in kmph) the brakes take off `speed` over this step, no more than it has
+    fn brake_loss(&self, speed: f64) -> f64 {
+        (self.effective_braking * BRAKE_DECELERATION * self.dt * 3.6).min(speed.abs())
+    }
+
+    // Speed (in kmph) after gaining no more than the engine's power allows from `initial_speed`,
+    // up a steep enough hill that is a loss
+    fn limit_power(&mut self, initial_speed: f64, speed: f64) -> f64 {
+        if self.throttle() == 0.0 {
+            return speed;
+        }
+        let power = self.throttle() * MAX_POWER * 1000.0;
+        let push = power / (self.config.mass * (initial_speed.abs() / 3.6).max(1.0));
+        let max_gain = (push - self.road_deceleration()) * self.dt * 3.6;
+        if (speed - initial_speed) * speed.signum() <= max_gain {
+            return speed;
+        }
+        let speed = initial_speed + max_gain * speed.signum();
+        self.instantaneous_speeds[0] = speed;
+
+        speed
+    }
+
+    // Speed (in kmph) after gaining no more than the tires can grip from `initial_speed`
+    fn limit_traction(&mut self, initial_speed: f64, speed: f64) -> f64 {
+        let max_gain = self.surface.grip() * GRAVITY * self.dt * 3.6;
+        if (speed - initial_speed) * speed.signum() <= max_gain {
+            return speed;
+        }
+        self.traction_limited = true;
+        let speed = initial_speed + max_gain * speed.signum();
+        self.instantaneous_speeds[0] = speed;
+
+        speed
+    }
+
+    /// Acceleration in the last update was limited by tire grip rather than engine power
+    pub fn traction_limited(&self) -> bool {
+        self.traction_limited
+    }
+
+    /// Rolling resistance, aerodynamic drag and the slope at the current speed, in m/s² against
+    /// the direction of travel
+    fn road_deceleration(&self) -> f64 {
+        let speed = self.speed / 3.6;
+        let rolling = GRAVITY * self.surface.rolling_resistance();
+        let drag = 0.5 * AIR_DENSITY * self.air_density() * self.config.drag_area * speed.powi(2)
+            / self.config.mass;
+
+        rolling + drag + self.slope_deceleration() * self.speed.signum()
+    }
+
+    /// Gravity along the road in m/s², negative downhill
+    fn slope_deceleration(&self) -> f64 {
+        GRAVITY * self.gradient.atan().sin()
+    }
+
+    /// Power in kW the road takes to hold the current speed, nothing when it helps
+    fn road_power(&self) -> f64 {
+        (self.config.mass * self.road_deceleration() * self.speed.abs() / 3.6 / 1000.0).max(0.0)
+    }
+
+    /// Only on emergency vehicles, see [`CarConfig::emergency_vehicle`]
+    pub fn set_sirens(&mut self, on: bool) {
+        self.sirens = on && self.config.emergency_vehicle;
+    }
+
+    pub fn sirens(&self) -> bool {
+        self.sirens
+    }
+
+    /// Switch the cabin heater on with its thermostat settings, or off with `None`
+    pub fn set_heater(&mut self, heater: Option<Heater>) {
+        self.cabin.heater = heater;
+    }
+
+    pub fn heater(&self) -> Option<Heater> {
+        self.cabin.heater
+    }
+
+    /// In °C
+    pub fn cabin_temperature(&self) -> f64 {
+        self.cabin.temperature
+    }
+
+    /// Electrical load of the accessories in kW, carried by the engine through the alternator
+    pub fn accessory_load(&self) -> f64 {
+        self.cabin.load()
+    }
+
+    // Lowest of the drive mode's top speed, the speed limiter and limp mode, in kmph. Sirens
+    // lift the speed limiter.
+    fn speed_cap(&self) -> f64 {
+        let limp_speed = self.limp.map(|_| LIMP_SPEED);
+        let speed_limiter = self.config.speed_limiter.filter(|_| !self.sirens);
+        [self.drive_mode.top_speed(), speed_limiter, limp_speed]
+            .into_iter()
+            .flatten()
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Brake system air pressure in bar, `None` without air brakes
+    pub fn air_pressure(&self) -> Option<f64> {
+        self.config.air_brakes.as_ref().map(|_| self.air_brake.pressure)
+    }
+
+    pub fn tachograph(&self) -> Option<&Tachograph> {
+        self.config.tachograph.then_some(&self.tachograph)
     }
 
     pub fn speed(&self) -> f64 {
         self.speed
     }
 
+    /// Model speed before smoothing, in kmph
+    pub fn speed_raw(&self) -> f64 {
+        self.raw_speed
+    }
+
+    /// Smoothed speed, same as [`Car::speed`]
+    pub fn speed_filtered(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn speed_mph(&self) -> f64 {
+        self.speed / KM_PER_MILE
+    }
+
+    /// Distance actually driven in km, see [`Car::odometer`] for what the car reports
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+
+    /// Odometer reading in km, which is what telemetry reports
+    pub fn odometer(&self) -> f64 {
+        match &self.odometer_tamper {
+            Some(tampering) => tampering.reading(self.distance),
+            None => self.distance,
+        }
+    }
+
+    /// Make the odometer diverge from the distance driven from now on, `None` makes it read
+    /// the distance driven again
+    pub fn tamper_odometer(&mut self, tamper: Option<OdometerTamper>) {
+        let since = (self.distance, self.odometer());
+        self.odometer_tamper = tamper.map(|tamper| Tampering { tamper, since });
+    }
+
+    pub fn odometer_tamper(&self) -> Option<OdometerTamper> {
+        self.odometer_tamper.map(|tampering| tampering.tamper)
+    }
+
+    pub fn distance_miles(&self) -> f64 {
+        self.distance / KM_PER_MILE
+    }
+
+    fn update_distance(&mut self) {
+        self.distance += self.speed.abs() * self.dt / 3600.0;
+        if let Some(obstacle_distance) = &mut self.obstacle_distance {
+            *obstacle_distance -= self.speed * self.dt / 3.6;
+        }
+        // Speed is negative in reverse
+        if let Some(rear_obstacle_distance) = &mut self.rear_obstacle_distance {
+            *rear_obstacle_distance += self.speed * self.dt / 3.6;
+        }
+    }
+
+    /// Road surface the car drives on from now on
+    pub fn set_surface(&mut self, surface: Surface) {
+        self.surface = surface;
+    }
+
+    pub fn surface(&self) -> Surface {
+        self.surface
+    }
+
+    /// Altitude in m the car drives at from now on
+    pub fn set_altitude(&mut self, altitude: f64) {
+        self.altitude = altitude;
+    }
+
+    pub fn altitude(&self) -> f64 {
+        self.altitude
+    }
+
+    /// Gradient of the road from now on as rise over run, e.g. 0.05 up a 5% grade, negative
+    /// downhill
+    pub fn set_gradient(&mut self, gradient: f64) {
+        self.gradient = gradient;
+    }
+
+    pub fn gradient(&self) -> f64 {
+        self.gradient
+    }
+
+    /// Air density relative to sea level
+    pub fn air_density(&self) -> f64 {
+        (-self.altitude / SCALE_HEIGHT).exp()
+    }
+
+    /// Apply an external disturbance, see [`RandomEvents`] for generating them
+    pub fn disturb(&mut self, disturbance: Disturbance) {
+        match disturbance {
+            Disturbance::Pothole { speed_loss } | Disturbance::Debris { speed_loss } => {
+                self.speed = (self.speed - speed_loss).max(0.0);
+                self.raw_speed = self.speed;
+                self.instantaneous_speeds = vec![self.speed];
+                self.road_shock += speed_loss;
+            }
+            Disturbance::SuddenObstacle { distance } => self.obstacle_distance = Some(distance),
+            Disturbance::FuelTheft { amount } => {
+                self.fuel_level = (self.fuel_level - amount).max(0.0);
+            }
+            // Only forward speed, the car barely moves in reverse
+            Disturbance::Gust { speed_change } if self.speed > 0.0 => {
+                self.speed = (self.speed + speed_change).max(0.0);
+                self.raw_speed = self.speed;
+                self.instantaneous_speeds = vec![self.speed];
+            }
+            Disturbance::Gust { .. } => {}
+        }
+        self.emit(Event::Disturbance(disturbance));
+    }
+
+    pub fn deceleration(&self) -> Deceleration {
+        self.deceleration
+    }
+
+    #[cfg(feature = "debug-state")]
+    pub fn debug_state(&self) -> DebugState {
+        DebugState {
+            throttle: self.throttle(),
+            engine_power: self.engine_load(),
+            wheel_speed: self.wheel_speed(),
+            raw_speed: self.raw_speed,
+            engine_drag: self.engine_drag(),
+            road_deceleration: self.road_deceleration(),
+            raw_braking: self.raw_braking,
+            effective_braking: self.effective_braking,
+            deceleration: self.deceleration,
+            speed_alpha: smoothing_alpha(self.config.speed_smoothing, self.dt),
+            braking_alpha: smoothing_alpha(self.config.braking_smoothing, self.dt),
+            speed_filter: self.instantaneous_speeds.clone(),
+            braking_filter: self.instantaneous_braking.clone(),
+        }
+    }
+
+    // In kW, what the engine puts out to turn itself over, drive the wheels and run accessories
+    fn engine_load(&self) -> f64 {
+        if self.engine_rpm == 0 {
+            return 0.0;
+        }
+        let friction = FRICTION_POWER * self.engine_rpm as f64 / BASE_RPM;
+        friction + self.wheel_power + self.accessory_load()
+    }
+
     pub fn update_fuel(&mut self) {
-        let power_output = self.engine_rpm as f64 * MAX_TORQUE * (2.0 * PI) / (60.0 * 1000.0);
-        let power_output = power_output.min(MAX_POWER) * 5.0 / self.transmission_ratio();
-        let fuel_consumption = power_output * BSFC;
-        self.fuel_level -= fuel_consumption * 10_f64.powi(-10);
+        // kW at BSFC in g/kWh gives grams an hour
+        let litres =
+            self.engine_load() * self.config.bsfc / 1000.0 / FUEL_DENSITY / 3600.0 * self.dt;
+        self.fuel_level -= litres / self.config.tank_capacity;
         self.fuel_level = self.fuel_level.max(0.0);
     }
 
@@ -216,29 +1341,216 @@ impl Car {
         self.fuel_level %= 1.0; // Max fuel level is 100%, i.e. 1.0
     }
 
+    /// Share of the tank, 0.0..=1.0
     pub fn fuel_level(&self) -> f64 {
         self.fuel_level
     }
 
+    /// Share of the tank, 0.0..=100.0
+    pub fn fuel_percent(&self) -> f64 {
+        self.fuel_level * 100.0
+    }
+
+    /// Efficiency over the last `config.efficiency_window` seconds, `None` while the car
+    /// hasn't been driving
+    pub fn efficiency(&self) -> Option<Efficiency> {
+        self.efficiency.efficiency()
+    }
+
+    /// NVH proxy standing in for an accelerometer, combining engine, road and shock vibration
+    pub fn vibration(&self) -> f64 {
+        self.vibration
+    }
+
+    fn update_vibration(&mut self) {
+        let load = 0.5 + 0.5 * self.throttle();
+        let engine = ENGINE_VIBRATION * (self.engine_rpm as f64 / MAX_RPM).powi(2) * load;
+        let road = ROAD_VIBRATION * self.surface.roughness() * self.speed.abs() / 100.0;
+        self.vibration = (engine.powi(2) + road.powi(2) + self.road_shock.powi(2)).sqrt();
+        self.road_shock *= SHOCK_DECAY.powf(self.dt);
+    }
+
+    pub fn maintenance(&self) -> &MaintenanceState {
+        &self.maintenance
+    }
+
+    pub fn set_service_interval(&mut self, item: MaintenanceItem, interval: f64) {
+        self.maintenance.get_mut(item).service_interval = interval;
+    }
+
+    /// Item was serviced, its wear starts over
+    pub fn service(&mut self, item: MaintenanceItem) {
+        self.maintenance.get_mut(item).usage = 0.0;
+    }
+
+    fn update_maintenance(&mut self) {
+        let hours = self.dt / 3600.0;
+        let running = self.power_state == PowerState::Ready && self.engine_rpm > 0;
+        let clutch_slipping = running
+            && !matches!(self.gear, Gear::Neutral | Gear::Park)
+            && self.clutch_position > 0.0
+            && self.clutch_position < 1.0;
+        let wear = [
+            (MaintenanceItem::BrakePads, self.deceleration.brakes),
+            (MaintenanceItem::Tires, self.speed.abs() * hours * self.config.tire_wear),
+            (MaintenanceItem::Clutch, if clutch_slipping { hours } else { 0.0 }),
+            (MaintenanceItem::CoolantPump, if running { hours } else { 0.0 }),
+            (MaintenanceItem::Battery, hours),
+        ];
+        for (item, usage) in wear {
+            if self.maintenance.wear(item, usage) {
+                self.emit(Event::ServiceDue { item });
+            }
+        }
+    }
+
+    pub fn limits(&self) -> Limits {
+        // Engine at the redline in the tallest forward gear
+        let ratios = &self.config.gear_ratios;
+        let tallest = [ratios.first, ratios.second, ratios.third, ratios.fourth, ratios.fifth]
+            .into_iter()
+            .fold(f64::INFINITY, f64::min);
+        let wheel_rpm = MAX_RPM / tallest / self.config.final_drive;
+        let max_speed =
+            (wheel_rpm * self.config.tire.circumference() * 60.0 / 1000.0).min(self.speed_cap());
+
+        Limits {
+            units: self.config.units,
+            max_speed: self.config.units.speed(max_speed),
+            max_rpm: MAX_RPM as u32,
+            max_power: self.drive_mode.max_power()
+                * self.air_density()
+                * if self.limp.is_some() { LIMP_POWER } else { 1.0 },
+        }
+    }
+
+    pub fn telemetry(&self) -> Telemetry {
+        Telemetry {
+            version: TELEMETRY_VERSION,
+            limp_mode: self.limp.is_some(),
+            vin: self.identity.as_ref().map(|identity| identity.vin.clone()),
+            speed: self.config.units.speed(self.speed),
+            distance: self.config.units.distance(self.odometer()),
+            units: self.config.units,
+            rpm: self.engine_rpm,
+            gear: self.gear,
+            fuel_level: self.fuel_level,
+            accelerator_position: self.accelerator_position,
+            brake_position: self.brake_position,
+            clutch_position: self.clutch_position,
+            hand_brake: self.hand_brake.clone(),
+            hand_brake_travel: self.hand_brake_travel,
+            drive_mode: self.drive_mode,
+            power_state: self.power_state,
+            air_pressure: self.air_pressure(),
+            tachograph: self.tachograph().copied(),
+            rear_brake_temperature: self.rear_brake_temperature,
+            aeb_active: self.aeb_active,
+            vibration: self.vibration,
+            driver: self.drivers.active().map(str::to_owned),
+            parking_sensor: self.parking_sensor(),
+            altitude: self.altitude,
+            efficiency: self.efficiency(),
+            counters: self.counters,
+            brake_light: self.brake_light(),
+            reverse_light: self.reverse_light(),
+            ready_lamp: self.ready_lamp(),
+            sirens: self.sirens,
+            wheel_speeds: self.wheel_speeds().map(|speed| self.config.units.speed(speed)),
+            traction_limited: self.traction_limited,
+            accessory_load: self.accessory_load(),
+            cabin_temperature: self.cabin.temperature,
+        }
+    }
+
+    /// Advance the car by `config.time_step`, split into sub-steps no longer than
+    /// `config.max_step` so results don't depend on the tick length
     pub fn update(&mut self) {
+        self.update_by(self.config.time_step);
+    }
+
+    /// Advance the car by `time_step` seconds instead of `config.time_step`, e.g. to catch up
+    /// after falling behind real time. Sub-steps are no longer than `config.max_step`, or
+    /// `config.time_step` without it.
+    pub fn update_by(&mut self, time_step: f64) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!(
+            "update",
+            vin = self.identity.as_ref().map(|identity| identity.vin.as_str())
+        )
+        .entered();
+        let (distance, fuel_level) = (self.distance, self.fuel_level);
+        self.update_power_state(time_step);
+        self.update_pedal_overlap();
+
+        let max_step = self.config.max_step.unwrap_or(self.config.time_step);
+        let steps = match max_step {
+            max_step if max_step > 0.0 => (time_step / max_step).ceil().max(1.0) as u32,
+            _ => 1,
+        };
+        self.dt = time_step / steps as f64;
+        let mut deceleration = Deceleration::default();
+        self.traction_limited = false;
+        for _ in 0..steps {
+            self.step();
+            deceleration.brakes += self.deceleration.brakes;
+            deceleration.engine += self.deceleration.engine;
+            deceleration.road += self.deceleration.road;
+        }
+        self.deceleration = deceleration;
+
+        let driving_time = if self.speed != 0.0 { time_step } else { 0.0 };
+        let fuel_used = (fuel_level - self.fuel_level).max(0.0);
+        self.drivers.record(self.distance - distance, driving_time, fuel_used);
+        self.efficiency.record(
+            time_step,
+            self.distance - distance,
+            fuel_used * self.config.tank_capacity,
+            self.config.efficiency_window,
+        );
+        self.update_chimes();
+        self.watchdog();
+    }
+
+    /// Reset the car rather than report NaN or infinite state forever
+    fn watchdog(&mut self) {
+        let state = [
+            ("speed", self.speed),
+            ("raw_speed", self.raw_speed),
+            ("distance", self.distance),
+            ("fuel_level", self.fuel_level),
+            ("accelerator_position", self.accelerator_position),
+            ("brake_position", self.brake_position),
+            ("clutch_position", self.clutch_position),
+            ("effective_braking", self.effective_braking),
+            ("transmission_rpm", self.transmission_rpm),
+            ("rear_brake_temperature", self.rear_brake_temperature),
+            ("vibration", self.vibration),
+            ("cabin_temperature", self.cabin.temperature),
+        ];
+        if let Some((field, _)) = state.into_iter().find(|(_, value)| !value.is_finite()) {
+            // Events from earlier in the update haven't been drained yet
+            let events = std::mem::take(&mut self.events);
+            self.reset();
+            self.events = events;
+            self.emit(Event::SimulationFault { field });
+        }
+    }
+
+    fn step(&mut self) {
+        self.update_aeb();
         self.update_rpm();
+        self.update_hand_brake();
         self.update_braking();
         self.update_speed();
+        self.update_distance();
+        if self.config.tachograph {
+            self.tachograph.update(self.speed != 0.0, self.dt);
+        }
+        self.cabin.update(self.power_state == PowerState::Ready, self.dt);
         self.update_fuel();
+        self.update_vibration();
+        self.update_maintenance();
+        self.update_counters();
     }
 }
-
-// Consider the vehicle's instantaneous speeds were: [15.2, 60.4]
-// We need to ensure that the instantaneous speeds are a bit more realistic,
-// so we use the exponential moving average(alpha = 0.7): 46.84
-fn exponential_moving_average(instantaneous_values: &[f64], alpha: f64) -> Vec<f64> {
-    let mut instantaneous_values = instantaneous_values.iter();
-    let mut last_value = *instantaneous_values.next().unwrap();
-    let mut ema = vec![last_value];
-    for value in instantaneous_values {
-        last_value = alpha * value + (1.0 - alpha) * last_value;
-        ema.push(last_value);
-    }
-
-    ema
-}
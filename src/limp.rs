@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Critical faults that put the car in limp-home mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Fault {
+    BrakeOverheat,
+    LowAirPressure,
+}
+
+/// Key signals at the moment a fault was raised, as kept with an OBD trouble code
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FreezeFrame {
+    /// in kmph
+    pub speed: f64,
+    pub rpm: u32,
+    pub fuel_level: f64,
+    /// in °C
+    pub rear_brake_temperature: f64,
+    /// in bar, for vehicles with air brakes
+    pub air_pressure: Option<f64>,
+    pub accelerator_position: f64,
+    pub brake_position: f64,
+    pub clutch_position: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LimpMode {
+    pub fault: Fault,
+    pub freeze_frame: FreezeFrame,
+    /// fault was cleared, the car leaves limp mode on the next key off
+    pub cleared: bool,
+}
@@ -0,0 +1,95 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MaintenanceItem {
+    /// usage is speed shed by the brakes, in kmph
+    BrakePads,
+    /// usage in km
+    Tires,
+    /// usage is time spent slipping, in h
+    Clutch,
+    /// usage is engine running time, in h
+    CoolantPump,
+    /// 12V battery, usage is its age in h
+    Battery,
+}
+
+impl MaintenanceItem {
+    pub const ALL: [MaintenanceItem; 5] = [
+        MaintenanceItem::BrakePads,
+        MaintenanceItem::Tires,
+        MaintenanceItem::Clutch,
+        MaintenanceItem::CoolantPump,
+        MaintenanceItem::Battery,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WearCounter {
+    /// since the item was last serviced
+    pub usage: f64,
+    pub service_interval: f64,
+}
+
+impl WearCounter {
+    fn new(service_interval: f64) -> Self {
+        Self { usage: 0.0, service_interval }
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.usage >= self.service_interval
+    }
+}
+
+/// Wear of serviceable parts, see [`MaintenanceItem`] for what usage means for each
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MaintenanceState {
+    pub brake_pads: WearCounter,
+    pub tires: WearCounter,
+    pub clutch: WearCounter,
+    pub coolant_pump: WearCounter,
+    pub battery: WearCounter,
+}
+
+impl Default for MaintenanceState {
+    fn default() -> Self {
+        Self {
+            brake_pads: WearCounter::new(2_000_000.0),
+            tires: WearCounter::new(50_000.0),
+            clutch: WearCounter::new(100.0),
+            coolant_pump: WearCounter::new(5_000.0),
+            battery: WearCounter::new(4.0 * 365.0 * 24.0),
+        }
+    }
+}
+
+impl MaintenanceState {
+    pub fn get(&self, item: MaintenanceItem) -> &WearCounter {
+        match item {
+            MaintenanceItem::BrakePads => &self.brake_pads,
+            MaintenanceItem::Tires => &self.tires,
+            MaintenanceItem::Clutch => &self.clutch,
+            MaintenanceItem::CoolantPump => &self.coolant_pump,
+            MaintenanceItem::Battery => &self.battery,
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, item: MaintenanceItem) -> &mut WearCounter {
+        match item {
+            MaintenanceItem::BrakePads => &mut self.brake_pads,
+            MaintenanceItem::Tires => &mut self.tires,
+            MaintenanceItem::Clutch => &mut self.clutch,
+            MaintenanceItem::CoolantPump => &mut self.coolant_pump,
+            MaintenanceItem::Battery => &mut self.battery,
+        }
+    }
+
+    /// Returns true if this pushed the item past its service interval
+    pub(crate) fn wear(&mut self, item: MaintenanceItem, usage: f64) -> bool {
+        let counter = self.get_mut(item);
+        let was_due = counter.is_due();
+        counter.usage += usage;
+
+        !was_due && counter.is_due()
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use serde::Deserialize;
+use toml::{Table, Value};
+
+use crate::Telemetry;
+
+/// Renames and rescales telemetry fields to match a downstream schema, e.g.
+/// ```toml
+/// [[field]]
+/// source = "speed"
+/// name = "vehicle_speed"
+/// scale = 0.27778 # kmph to m/s
+///
+/// [[field]]
+/// source = "fuel_level"
+/// scale = 100.0
+/// ```
+/// Only the listed fields are emitted.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelemetryMapping {
+    #[serde(rename = "field")]
+    pub fields: Vec<FieldMapping>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldMapping {
+    /// name of the field in [`Telemetry`]
+    pub source: String,
+    /// output name, defaults to `source`
+    pub name: Option<String>,
+    /// output = value * scale + offset, only valid on numeric fields
+    #[serde(default = "unit_scale")]
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+}
+
+fn unit_scale() -> f64 {
+    1.0
+}
+
+#[derive(Debug)]
+pub enum MappingError {
+    Parse(toml::de::Error),
+    UnknownField(String),
+    /// scale or offset given for a field that isn't a number
+    NotNumeric(String),
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MappingError::Parse(e) => write!(f, "invalid mapping: {e}"),
+            MappingError::UnknownField(field) => write!(f, "telemetry has no field {field}"),
+            MappingError::NotNumeric(field) => write!(f, "can't scale non-numeric field {field}"),
+        }
+    }
+}
+
+impl std::error::Error for MappingError {}
+
+impl TelemetryMapping {
+    pub fn from_toml(mapping: &str) -> Result<Self, MappingError> {
+        toml::from_str(mapping).map_err(MappingError::Parse)
+    }
+
+    pub fn apply(&self, telemetry: &Telemetry) -> Result<Table, MappingError> {
+        let Ok(Value::Table(source)) = Value::try_from(telemetry) else {
+            unreachable!("Telemetry always serializes into a table")
+        };
+
+        let mut mapped = Table::new();
+        for field in &self.fields {
+            let value = source
+                .get(&field.source)
+                .ok_or_else(|| MappingError::UnknownField(field.source.clone()))?;
+            let value = if field.scale == 1.0 && field.offset == 0.0 {
+                value.clone()
+            } else {
+                let number = match value {
+                    Value::Float(v) => *v,
+                    Value::Integer(v) => *v as f64,
+                    _ => return Err(MappingError::NotNumeric(field.source.clone())),
+                };
+                Value::Float(number * field.scale + field.offset)
+            };
+            mapped.insert(field.name.clone().unwrap_or_else(|| field.source.clone()), value);
+        }
+
+        Ok(mapped)
+    }
+}
@@ -0,0 +1,78 @@
+//! Smoothing the car applies to its speed and braking, for backends that need to reproduce
+//! the simulator's numbers exactly
+
+/// Weight of a new sample in an exponential moving average with the given time constant,
+/// sampled every `time_step` (both in s). Always within 0.0..=1.0, a time constant of 0.0 or
+/// less gives 1.0, i.e. no smoothing.
+pub fn smoothing_alpha(time_constant: f64, time_step: f64) -> f64 {
+    if time_constant <= 0.0 {
+        return 1.0;
+    }
+
+    1.0 - (-time_step / time_constant).exp()
+}
+
+/// Running exponential moving average of `values`, one output per input. The first value
+/// passes through as is, each next one is `alpha * value + (1.0 - alpha) * previous`:
+///
+/// ```
+/// use vehicle_dynamics::math::exponential_moving_average;
+///
+/// let ema = exponential_moving_average(&[15.2, 60.4], 0.7);
+/// assert_eq!(ema[0], 15.2);
+/// assert!((ema[1] - 46.84).abs() < 1e-9);
+/// ```
+pub fn exponential_moving_average(values: &[f64], alpha: f64) -> Vec<f64> {
+    let mut ema = Vec::with_capacity(values.len());
+    let mut values = values.iter();
+    let Some(&first) = values.next() else {
+        return ema;
+    };
+    let mut last_value = first;
+    ema.push(last_value);
+    for value in values {
+        last_value = alpha * value + (1.0 - alpha) * last_value;
+        ema.push(last_value);
+    }
+
+    ema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_time_constant_disables_smoothing() {
+        assert_eq!(smoothing_alpha(0.0, 1.0), 1.0);
+        assert_eq!(smoothing_alpha(-1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn zero_time_step_keeps_the_previous_value() {
+        assert_eq!(smoothing_alpha(1.0, 0.0), 0.0);
+        assert_eq!(
+            exponential_moving_average(&[10.0, 20.0], smoothing_alpha(1.0, 0.0)),
+            [10.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn large_time_step_approaches_the_new_value() {
+        let alpha = smoothing_alpha(1.0, 1000.0);
+        assert!((0.0..=1.0).contains(&alpha));
+        assert!(1.0 - alpha < 1e-9);
+    }
+
+    #[test]
+    fn alpha_stays_within_bounds() {
+        for (time_constant, time_step) in [(1.0, 1e-9), (0.5, 1.0), (1e-9, 1.0), (1.0, f64::MAX)] {
+            assert!((0.0..=1.0).contains(&smoothing_alpha(time_constant, time_step)));
+        }
+    }
+
+    #[test]
+    fn empty_input_gives_empty_output() {
+        assert!(exponential_moving_average(&[], 0.5).is_empty());
+    }
+}
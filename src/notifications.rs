@@ -0,0 +1,79 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{Chime, Event, Fault, MaintenanceItem};
+
+/// User-facing message a companion app would push for a car event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Notification {
+    ServiceDue(MaintenanceItem),
+    /// the car went into limp-home mode
+    Fault(Fault),
+    LowFuel,
+    /// AEB braked for an obstacle
+    EmergencyBraking,
+}
+
+impl Notification {
+    /// `None` for events a driver doesn't need to hear about
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::ServiceDue { item } => Some(Notification::ServiceDue(*item)),
+            Event::LimpModeEntered { fault, .. } => Some(Notification::Fault(*fault)),
+            Event::Chime { chime: Chime::LowFuel, .. } => Some(Notification::LowFuel),
+            Event::AebActivated { .. } => Some(Notification::EmergencyBraking),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Notification::ServiceDue(item) => write!(f, "{item:?} due for service"),
+            Notification::Fault(fault) => write!(f, "{fault:?}, power is limited"),
+            Notification::LowFuel => write!(f, "Fuel is running low"),
+            Notification::EmergencyBraking => write!(f, "Emergency braking was triggered"),
+        }
+    }
+}
+
+/// Turns the events of each update into notifications, holding back a repeat of one sent
+/// less than `cooldown` seconds ago
+#[derive(Debug, Clone)]
+pub struct Notifications {
+    pub cooldown: f64,
+    /// in s, since the first update
+    elapsed: f64,
+    /// when each notification was last sent
+    sent: Vec<(Notification, f64)>,
+}
+
+impl Notifications {
+    pub fn new(cooldown: f64) -> Self {
+        Self { cooldown, elapsed: 0.0, sent: vec![] }
+    }
+
+    /// Call once per update with the events it raised, e.g. from
+    /// [`Car::drain_events`](crate::Car::drain_events)
+    pub fn process<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a Event>,
+        time_step: f64,
+    ) -> Vec<Notification> {
+        self.elapsed += time_step;
+
+        let mut notifications = vec![];
+        for notification in events.into_iter().filter_map(Notification::from_event) {
+            match self.sent.iter_mut().find(|(sent, _)| *sent == notification) {
+                Some((_, at)) if self.elapsed - *at < self.cooldown => continue,
+                Some((_, at)) => *at = self.elapsed,
+                None => self.sent.push((notification, self.elapsed)),
+            }
+            notifications.push(notification);
+        }
+
+        notifications
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Odometer fraud, from when it starts the reading diverges from the distance actually driven
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OdometerTamper {
+    /// reading stops going up
+    Frozen,
+    /// reading goes up by this share of the distance driven, e.g. 0.5 to hide half of it
+    Scaled(f64),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Tampering {
+    pub tamper: OdometerTamper,
+    /// distance driven and odometer reading when tampering started, in km
+    pub since: (f64, f64),
+}
+
+impl Tampering {
+    pub(crate) fn reading(&self, distance: f64) -> f64 {
+        let (since_distance, since_reading) = self.since;
+        match self.tamper {
+            OdometerTamper::Frozen => since_reading,
+            OdometerTamper::Scaled(share) => since_reading + (distance - since_distance) * share,
+        }
+    }
+}
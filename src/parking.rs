@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Ultrasonic sensors don't see obstacles further away than this, in m
+const SENSOR_RANGE: f64 = 2.5;
+
+/// How the parking beeper sounds at a distance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum BeepZone {
+    /// obstacle detected but still far
+    Silent,
+    Slow,
+    Fast,
+    /// stop now
+    Continuous,
+}
+
+impl BeepZone {
+    pub fn from_distance(distance: f64) -> Self {
+        match distance {
+            d if d < 0.3 => BeepZone::Continuous,
+            d if d < 0.9 => BeepZone::Fast,
+            d if d < 1.5 => BeepZone::Slow,
+            _ => BeepZone::Silent,
+        }
+    }
+}
+
+/// Reading of the rear parking sensors
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ParkingSensor {
+    /// to the obstacle behind in m, 0.0 once touching it
+    pub distance: f64,
+    pub zone: BeepZone,
+}
+
+impl ParkingSensor {
+    /// `None` when the obstacle is out of range
+    pub(crate) fn read(obstacle_distance: f64) -> Option<Self> {
+        let distance = obstacle_distance.max(0.0);
+        (distance <= SENSOR_RANGE)
+            .then(|| ParkingSensor { distance, zone: BeepZone::from_distance(distance) })
+    }
+}
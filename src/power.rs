@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+// Transition times, in seconds
+pub(crate) const WAKE_TIME: f64 = 1.0;
+pub(crate) const CRANK_TIME: f64 = 2.0;
+pub(crate) const SLEEP_TIMEOUT: f64 = 60.0;
+
+/// Vehicle power state, the engine only runs when `Ready`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PowerState {
+    /// only keeps the modules needed for waking up powered
+    #[default]
+    Sleep,
+    /// key off but electronics awake, e.g. after a remote wake up or turning the key off
+    Accessory,
+    /// key on, engine cranking
+    IgnitionOn,
+    /// engine running
+    Ready,
+}
+
+/// What makes the power state change
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum PowerTrigger {
+    KeyOn,
+    KeyOff,
+    /// remote wake up completed
+    Wake,
+    /// engine started
+    Cranked,
+    /// left in accessory mode for too long
+    SleepTimeout,
+    /// engine died, e.g. ran out of fuel
+    Stall,
+}
+
+impl PowerTrigger {
+    pub const ALL: [PowerTrigger; 6] = [
+        PowerTrigger::KeyOn,
+        PowerTrigger::KeyOff,
+        PowerTrigger::Wake,
+        PowerTrigger::Cranked,
+        PowerTrigger::SleepTimeout,
+        PowerTrigger::Stall,
+    ];
+}
+
+impl PowerState {
+    pub const ALL: [PowerState; 4] =
+        [PowerState::Sleep, PowerState::Accessory, PowerState::IgnitionOn, PowerState::Ready];
+
+    /// The transition table the car follows, `None` if the trigger doesn't apply in this state
+    pub fn next(&self, trigger: PowerTrigger) -> Option<PowerState> {
+        use PowerState::*;
+        use PowerTrigger::*;
+
+        match (self, trigger) {
+            (Sleep | Accessory, KeyOn) => Some(IgnitionOn),
+            (IgnitionOn, Cranked) => Some(Ready),
+            (Ready, Stall) => Some(IgnitionOn),
+            (IgnitionOn | Ready, KeyOff) => Some(Accessory),
+            (Sleep, Wake) => Some(Accessory),
+            (Accessory, SleepTimeout) => Some(Sleep),
+            _ => None,
+        }
+    }
+
+    /// Every (from, trigger, to) transition in the table
+    pub fn transitions() -> Vec<(PowerState, PowerTrigger, PowerState)> {
+        PowerState::ALL
+            .iter()
+            .flat_map(|from| {
+                PowerTrigger::ALL
+                    .iter()
+                    .filter_map(move |trigger| Some((*from, *trigger, from.next(*trigger)?)))
+            })
+            .collect()
+    }
+
+    /// Transition table as a Graphviz DOT digraph, edges are labelled with their trigger
+    pub fn to_dot() -> String {
+        let mut dot = "digraph PowerState {\n".to_owned();
+        for state in PowerState::ALL {
+            dot += &format!("    {state:?};\n");
+        }
+        for (from, trigger, to) in PowerState::transitions() {
+            dot += &format!("    {from:?} -> {to:?} [label=\"{trigger:?}\"];\n");
+        }
+        dot += "}\n";
+
+        dot
+    }
+}
@@ -0,0 +1,281 @@
+//! Protobuf encoding of [`Telemetry`](crate::Telemetry), mirrors `proto/telemetry.proto`
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Telemetry {
+    #[prost(double, tag = "1")]
+    pub speed: f64,
+    #[prost(uint32, tag = "2")]
+    pub rpm: u32,
+    #[prost(enumeration = "Gear", tag = "3")]
+    pub gear: i32,
+    #[prost(double, tag = "4")]
+    pub fuel_level: f64,
+    #[prost(double, tag = "5")]
+    pub accelerator_position: f64,
+    #[prost(double, tag = "6")]
+    pub brake_position: f64,
+    #[prost(double, tag = "7")]
+    pub clutch_position: f64,
+    #[prost(enumeration = "HandBrake", tag = "8")]
+    pub hand_brake: i32,
+    #[prost(enumeration = "DriveMode", tag = "9")]
+    pub drive_mode: i32,
+    #[prost(enumeration = "PowerState", tag = "10")]
+    pub power_state: i32,
+    #[prost(string, optional, tag = "11")]
+    pub vin: Option<String>,
+    #[prost(double, tag = "12")]
+    pub distance: f64,
+    #[prost(enumeration = "Units", tag = "13")]
+    pub units: i32,
+    #[prost(double, optional, tag = "14")]
+    pub air_pressure: Option<f64>,
+    #[prost(message, optional, tag = "15")]
+    pub tachograph: Option<Tachograph>,
+    #[prost(double, tag = "16")]
+    pub rear_brake_temperature: f64,
+    #[prost(bool, tag = "17")]
+    pub aeb_active: bool,
+    #[prost(double, tag = "18")]
+    pub vibration: f64,
+    #[prost(string, optional, tag = "19")]
+    pub driver: Option<String>,
+    #[prost(double, tag = "20")]
+    pub hand_brake_travel: f64,
+    #[prost(uint32, tag = "21")]
+    pub version: u32,
+    #[prost(bool, tag = "22")]
+    pub limp_mode: bool,
+    #[prost(message, optional, tag = "23")]
+    pub parking_sensor: Option<ParkingSensor>,
+    #[prost(double, tag = "24")]
+    pub altitude: f64,
+    #[prost(message, optional, tag = "25")]
+    pub efficiency: Option<Efficiency>,
+    #[prost(message, optional, tag = "26")]
+    pub counters: Option<Counters>,
+    #[prost(bool, tag = "27")]
+    pub brake_light: bool,
+    #[prost(bool, tag = "28")]
+    pub reverse_light: bool,
+    #[prost(bool, tag = "29")]
+    pub ready_lamp: bool,
+    #[prost(bool, tag = "30")]
+    pub sirens: bool,
+    #[prost(message, optional, tag = "31")]
+    pub wheel_speeds: Option<WheelSpeeds>,
+    #[prost(bool, tag = "32")]
+    pub traction_limited: bool,
+    #[prost(double, tag = "33")]
+    pub accessory_load: f64,
+    #[prost(double, tag = "34")]
+    pub cabin_temperature: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Efficiency {
+    #[prost(double, tag = "1")]
+    pub wh_per_km: f64,
+    #[prost(double, tag = "2")]
+    pub km_per_kwh: f64,
+    #[prost(double, tag = "3")]
+    pub mpge: f64,
+    #[prost(double, tag = "4")]
+    pub litres_per_100km: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WheelSpeeds {
+    #[prost(double, tag = "1")]
+    pub front_left: f64,
+    #[prost(double, tag = "2")]
+    pub front_right: f64,
+    #[prost(double, tag = "3")]
+    pub rear_left: f64,
+    #[prost(double, tag = "4")]
+    pub rear_right: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Counters {
+    #[prost(uint32, tag = "1")]
+    pub key_cycles: u32,
+    #[prost(uint32, tag = "2")]
+    pub drive_sessions: u32,
+    #[prost(double, tag = "3")]
+    pub engine_hours: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Tachograph {
+    #[prost(double, tag = "1")]
+    pub continuous_driving: f64,
+    #[prost(double, tag = "2")]
+    pub total_driving: f64,
+    #[prost(double, tag = "3")]
+    pub current_rest: f64,
+    #[prost(double, tag = "4")]
+    pub total_rest: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ParkingSensor {
+    #[prost(double, tag = "1")]
+    pub distance: f64,
+    #[prost(enumeration = "BeepZone", tag = "2")]
+    pub zone: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Gear {
+    Neutral = 0,
+    First = 1,
+    Second = 2,
+    Third = 3,
+    Fourth = 4,
+    Fifth = 5,
+    Reverse = 6,
+    Park = 7,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum HandBrake {
+    Disengaged = 0,
+    Half = 1,
+    Full = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum DriveMode {
+    Normal = 0,
+    Eco = 1,
+    Sport = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum Units {
+    Metric = 0,
+    Imperial = 1,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum BeepZone {
+    Silent = 0,
+    Slow = 1,
+    Fast = 2,
+    Continuous = 3,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum PowerState {
+    Sleep = 0,
+    Accessory = 1,
+    IgnitionOn = 2,
+    Ready = 3,
+}
+
+impl From<&crate::Telemetry> for Telemetry {
+    fn from(telemetry: &crate::Telemetry) -> Self {
+        let gear = match telemetry.gear {
+            crate::Gear::Neutral => Gear::Neutral,
+            crate::Gear::First => Gear::First,
+            crate::Gear::Second => Gear::Second,
+            crate::Gear::Third => Gear::Third,
+            crate::Gear::Fourth => Gear::Fourth,
+            crate::Gear::Fifth => Gear::Fifth,
+            crate::Gear::Reverse => Gear::Reverse,
+            crate::Gear::Park => Gear::Park,
+        };
+        let hand_brake = match telemetry.hand_brake {
+            crate::HandBrake::Disengaged => HandBrake::Disengaged,
+            crate::HandBrake::Half => HandBrake::Half,
+            crate::HandBrake::Full => HandBrake::Full,
+        };
+        let drive_mode = match telemetry.drive_mode {
+            crate::DriveMode::Normal => DriveMode::Normal,
+            crate::DriveMode::Eco => DriveMode::Eco,
+            crate::DriveMode::Sport => DriveMode::Sport,
+        };
+        let power_state = match telemetry.power_state {
+            crate::PowerState::Sleep => PowerState::Sleep,
+            crate::PowerState::Accessory => PowerState::Accessory,
+            crate::PowerState::IgnitionOn => PowerState::IgnitionOn,
+            crate::PowerState::Ready => PowerState::Ready,
+        };
+
+        let units = match telemetry.units {
+            crate::Units::Metric => Units::Metric,
+            crate::Units::Imperial => Units::Imperial,
+        };
+
+        Self {
+            speed: telemetry.speed,
+            rpm: telemetry.rpm,
+            gear: gear as i32,
+            fuel_level: telemetry.fuel_level,
+            accelerator_position: telemetry.accelerator_position,
+            brake_position: telemetry.brake_position,
+            clutch_position: telemetry.clutch_position,
+            hand_brake: hand_brake as i32,
+            drive_mode: drive_mode as i32,
+            power_state: power_state as i32,
+            vin: telemetry.vin.clone(),
+            distance: telemetry.distance,
+            units: units as i32,
+            air_pressure: telemetry.air_pressure,
+            tachograph: telemetry.tachograph.map(|tachograph| Tachograph {
+                continuous_driving: tachograph.continuous_driving,
+                total_driving: tachograph.total_driving,
+                current_rest: tachograph.current_rest,
+                total_rest: tachograph.total_rest,
+            }),
+            rear_brake_temperature: telemetry.rear_brake_temperature,
+            aeb_active: telemetry.aeb_active,
+            vibration: telemetry.vibration,
+            driver: telemetry.driver.clone(),
+            hand_brake_travel: telemetry.hand_brake_travel,
+            version: telemetry.version,
+            limp_mode: telemetry.limp_mode,
+            parking_sensor: telemetry.parking_sensor.map(|sensor| {
+                let zone = match sensor.zone {
+                    crate::BeepZone::Silent => BeepZone::Silent,
+                    crate::BeepZone::Slow => BeepZone::Slow,
+                    crate::BeepZone::Fast => BeepZone::Fast,
+                    crate::BeepZone::Continuous => BeepZone::Continuous,
+                };
+                ParkingSensor { distance: sensor.distance, zone: zone as i32 }
+            }),
+            altitude: telemetry.altitude,
+            efficiency: telemetry.efficiency.map(|efficiency| Efficiency {
+                wh_per_km: efficiency.wh_per_km,
+                km_per_kwh: efficiency.km_per_kwh,
+                mpge: efficiency.mpge,
+                litres_per_100km: efficiency.litres_per_100km,
+            }),
+            counters: Some(Counters {
+                key_cycles: telemetry.counters.key_cycles,
+                drive_sessions: telemetry.counters.drive_sessions,
+                engine_hours: telemetry.counters.engine_hours,
+            }),
+            brake_light: telemetry.brake_light,
+            reverse_light: telemetry.reverse_light,
+            ready_lamp: telemetry.ready_lamp,
+            sirens: telemetry.sirens,
+            wheel_speeds: Some(WheelSpeeds {
+                front_left: telemetry.wheel_speeds.front_left,
+                front_right: telemetry.wheel_speeds.front_right,
+                rear_left: telemetry.wheel_speeds.rear_left,
+                rear_right: telemetry.wheel_speeds.rear_right,
+            }),
+            traction_limited: telemetry.traction_limited,
+            accessory_load: telemetry.accessory_load,
+            cabin_temperature: telemetry.cabin_temperature,
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use rand::Rng;
+
+use crate::{distribution::happens, Car, Distribution, Gear, HandBrake};
+
+/// Driver making random but plausible choices every update: shifting around the rev range,
+/// braking now and then and varying the throttle. Choices are drawn from configurable
+/// distributions, so the generated behaviour can be fitted to real fleet data. Chances outside
+/// 0.0..=1.0 are clamped, NaN counts as 0.0.
+#[derive(Debug, Clone)]
+pub struct RandomDriver {
+    /// accelerator position
+    pub throttle: Distribution,
+    /// brake position, whenever the driver brakes
+    pub brake: Distribution,
+    /// chance of starting to brake in an update
+    pub brake_chance: f64,
+    /// clutch position while shifting
+    pub shift_clutch: Distribution,
+    /// chance of shifting in an update while the engine is above 2500 rpm, the driver always
+    /// shifts outside 1250-3500 rpm
+    pub shift_chance: f64,
+    /// chance of pulling the hand brake in an update
+    pub hand_brake_chance: f64,
+    /// time in s the driver holds the throttle before choosing again, `None` chooses every
+    /// update
+    pub dwell: Option<Distribution>,
+    holding: f64,
+}
+
+impl Default for RandomDriver {
+    fn default() -> Self {
+        Self {
+            throttle: Distribution::Uniform { low: 0.25, high: 1.0 },
+            brake: Distribution::Uniform { low: 0.3, high: 1.0 },
+            brake_chance: 0.05,
+            shift_clutch: Distribution::Uniform { low: 0.25, high: 1.0 },
+            shift_chance: 0.05,
+            hand_brake_chance: 0.005,
+            dwell: None,
+            holding: 0.0,
+        }
+    }
+}
+
+impl RandomDriver {
+    /// Emergency response driving: throttle mostly floored, hard and frequent braking, and
+    /// quick shifts
+    pub fn emergency() -> Self {
+        Self {
+            throttle: Distribution::Beta { alpha: 5.0, beta: 1.0, low: 0.5, high: 1.0 },
+            brake: Distribution::Uniform { low: 0.6, high: 1.0 },
+            brake_chance: 0.1,
+            shift_clutch: Distribution::Uniform { low: 0.6, high: 1.0 },
+            shift_chance: 0.1,
+            hand_brake_chance: 0.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_dwell(mut self, dwell: Distribution) -> Self {
+        self.dwell = Some(dwell);
+        self
+    }
+
+    /// Call once per update, before updating the car
+    pub fn drive(&mut self, car: &mut Car, rng: &mut impl Rng) {
+        if happens(rng, self.shift_chance) && car.rpm() > 2500
+            || car.rpm() > 3500
+            || car.rpm() < 1250
+        {
+            shift_gears(car, self.shift_clutch.sample_clamped(rng, 0.0, 1.0));
+        } else {
+            car.set_clutch_position(0.0);
+        }
+
+        // very few times, press the brake to slow down, else remove
+        if happens(rng, self.brake_chance) || car.brake_position() > 0.5 {
+            car.set_brake_position(self.brake.sample_clamped(rng, 0.0, 1.0));
+            return;
+        } else {
+            car.set_brake_position(0.0);
+        }
+
+        // even fewer times, engage hand brake to slow down instantly, or else do the opposite
+        if happens(rng, self.hand_brake_chance) {
+            if rng.gen_bool(0.25) || car.hand_brake() == &HandBrake::Half {
+                car.set_handbrake_position(HandBrake::Full);
+                return;
+            } else {
+                car.set_handbrake_position(HandBrake::Half);
+            }
+        } else if car.hand_brake() != &HandBrake::Disengaged {
+            car.set_handbrake_position(
+                if rng.gen_bool(0.25) || car.hand_brake() == &HandBrake::Full {
+                    HandBrake::Half
+                } else {
+                    HandBrake::Disengaged
+                },
+            );
+        }
+
+        self.holding -= car.config().time_step;
+        if self.holding <= 0.0 {
+            car.set_accelerator_position(self.throttle.sample_clamped(rng, 0.0, 1.0));
+            self.holding = self.dwell.map_or(0.0, |dwell| rng.sample(dwell));
+        }
+    }
+}
+
+fn shift_gears(car: &mut Car, clutch_position: f64) {
+    let clutch_gear_combo = |car: &mut Car, gear| {
+        car.set_clutch_position(clutch_position);
+        car.shift_gear(gear);
+    };
+    match car.gear() {
+        Gear::Park | Gear::Reverse => clutch_gear_combo(car, Gear::Neutral),
+        Gear::Neutral => {
+            if car.clutch_position() > 0.5 {
+                clutch_gear_combo(car, Gear::First)
+            }
+        }
+        Gear::First => {
+            if car.rpm() > 2500 && car.speed() > 10.0 {
+                clutch_gear_combo(car, Gear::Second)
+            }
+        }
+        Gear::Second => match car.speed() as u8 {
+            0..=10 => clutch_gear_combo(car, Gear::First),
+            s if s > 25 && car.rpm() > 3000 => clutch_gear_combo(car, Gear::Third),
+            _ => {}
+        },
+        Gear::Third => match car.speed() as u8 {
+            0..=10 => clutch_gear_combo(car, Gear::First),
+            11..=20 => clutch_gear_combo(car, Gear::Second),
+            s if s > 50 && car.rpm() > 3500 => clutch_gear_combo(car, Gear::Fourth),
+            _ => {}
+        },
+        Gear::Fourth => match car.speed() as u8 {
+            0..=10 => clutch_gear_combo(car, Gear::First),
+            11..=20 => clutch_gear_combo(car, Gear::Second),
+            21..=40 => clutch_gear_combo(car, Gear::Third),
+            s if s > 80 && car.rpm() > 4000 => clutch_gear_combo(car, Gear::Fifth),
+            _ => {}
+        },
+        Gear::Fifth => match car.speed() as u8 {
+            0..=10 => clutch_gear_combo(car, Gear::First),
+            11..=20 => clutch_gear_combo(car, Gear::Second),
+            21..=40 => clutch_gear_combo(car, Gear::Third),
+            41..=70 => clutch_gear_combo(car, Gear::Fourth),
+            _ => {}
+        },
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
+use serde::Serialize;
+
+#[cfg(feature = "rand")]
+use crate::{distribution::happens, Car, PowerState};
+
+/// External disturbance hitting the car, reported as an event so it doubles as ground truth
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Disturbance {
+    /// sharp jolt, costing some speed (in kmph)
+    Pothole { speed_loss: f64 },
+    /// something appears on the road ahead (in m)
+    SuddenObstacle { distance: f64 },
+    /// driving over debris, a smaller jolt than a pothole
+    Debris { speed_loss: f64 },
+    /// fuel siphoned off while parked, as a share of the tank
+    FuelTheft { amount: f64 },
+    /// sudden head or tail wind, pushing the car back or forward (in kmph, negative for a
+    /// headwind)
+    Gust { speed_change: f64 },
+}
+
+/// Generates disturbances at random, rates are in expected occurrences per hour of driving,
+/// or of being parked for fuel theft. A negative or NaN rate never fires.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, Default)]
+pub struct RandomEvents {
+    pub pothole: f64,
+    pub obstacle: f64,
+    pub debris: f64,
+    pub fuel_theft: f64,
+    /// largest share of the tank taken in one theft
+    pub fuel_theft_amount: f64,
+    pub gust: f64,
+    /// largest speed change from a gust at 100 kmph in kmph, drag grows with the square of speed
+    pub gust_intensity: f64,
+    /// an obstacle we placed is still on the road
+    placed_obstacle: bool,
+}
+
+#[cfg(feature = "rand")]
+impl RandomEvents {
+    pub fn new(pothole: f64, obstacle: f64, debris: f64) -> Self {
+        Self { pothole, obstacle, debris, ..Default::default() }
+    }
+
+    pub fn with_fuel_theft(mut self, rate: f64, amount: f64) -> Self {
+        self.fuel_theft = rate;
+        self.fuel_theft_amount = amount;
+        self
+    }
+
+    pub fn with_gusts(mut self, rate: f64, intensity: f64) -> Self {
+        self.gust = rate;
+        self.gust_intensity = intensity;
+        self
+    }
+
+    /// Call once per update, returns the disturbance that was applied to the car if any
+    pub fn apply(&mut self, car: &mut Car, rng: &mut impl Rng) -> Option<Disturbance> {
+        // Obstacle clears once the car has stopped for it
+        if self.placed_obstacle && car.speed() == 0.0 {
+            car.set_obstacle_distance(None);
+            self.placed_obstacle = false;
+        }
+
+        let hours = car.config().time_step / 3600.0;
+        let disturbance = if car.speed() == 0.0 {
+            let parked = car.power_state() != PowerState::Ready;
+            if !parked || self.fuel_theft_amount <= 0.0 || !happens(rng, self.fuel_theft * hours) {
+                return None;
+            }
+            let amount = rng.gen_range(self.fuel_theft_amount / 2.0..=self.fuel_theft_amount);
+            Disturbance::FuelTheft { amount }
+        } else if happens(rng, self.pothole * hours) {
+            Disturbance::Pothole { speed_loss: rng.gen_range(1.0..4.0) }
+        } else if !self.placed_obstacle && happens(rng, self.obstacle * hours) {
+            self.placed_obstacle = true;
+            Disturbance::SuddenObstacle { distance: rng.gen_range(10.0..40.0) }
+        } else if happens(rng, self.debris * hours) {
+            Disturbance::Debris { speed_loss: rng.gen_range(0.2..1.0) }
+        } else if self.gust_intensity > 0.0 && happens(rng, self.gust * hours) {
+            let drag = (car.speed() / 100.0).powi(2);
+            let speed_change = rng.gen_range(-self.gust_intensity..=self.gust_intensity) * drag;
+            Disturbance::Gust { speed_change }
+        } else {
+            return None;
+        };
+        car.disturb(disturbance.clone());
+
+        Some(disturbance)
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{Car, PowerState};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub enum RentalState {
+    #[default]
+    Available,
+    Reserved,
+    Unlocked,
+    InTrip,
+    TripEnded,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RentalError {
+    /// action isn't allowed in the session's current state
+    InvalidState { action: &'static str, state: RentalState },
+    /// trip can only end with the car stationary
+    Moving,
+    /// trip can only end with the engine off
+    EngineRunning,
+}
+
+impl fmt::Display for RentalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RentalError::InvalidState { action, state } => {
+                write!(f, "can't {action} while {state:?}")
+            }
+            RentalError::Moving => write!(f, "car is still moving"),
+            RentalError::EngineRunning => write!(f, "engine is still running"),
+        }
+    }
+}
+
+impl std::error::Error for RentalError {}
+
+/// Usage over a single trip
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct TripSummary {
+    /// in km
+    pub distance: f64,
+    /// share of the tank, net of any refuelling during the trip
+    pub fuel_used: f64,
+}
+
+/// Car-sharing lifecycle: reserve, unlock, start trip, end trip and lock, which makes the car
+/// available again
+#[derive(Debug, Clone, Default)]
+pub struct RentalSession {
+    state: RentalState,
+    /// odometer and fuel level when the trip started
+    start: (f64, f64),
+    summary: Option<TripSummary>,
+}
+
+impl RentalSession {
+    pub fn state(&self) -> RentalState {
+        self.state
+    }
+
+    /// Summary of the last trip that ended
+    pub fn summary(&self) -> Option<TripSummary> {
+        self.summary
+    }
+
+    fn transition(
+        &mut self,
+        action: &'static str,
+        from: RentalState,
+        to: RentalState,
+    ) -> Result<(), RentalError> {
+        if self.state != from {
+            return Err(RentalError::InvalidState { action, state: self.state });
+        }
+        self.state = to;
+
+        Ok(())
+    }
+
+    pub fn reserve(&mut self) -> Result<(), RentalError> {
+        self.transition("reserve", RentalState::Available, RentalState::Reserved)
+    }
+
+    pub fn unlock(&mut self) -> Result<(), RentalError> {
+        self.transition("unlock", RentalState::Reserved, RentalState::Unlocked)
+    }
+
+    pub fn start_trip(&mut self, car: &Car) -> Result<(), RentalError> {
+        self.transition("start trip", RentalState::Unlocked, RentalState::InTrip)?;
+        self.start = (car.distance(), car.fuel_level());
+
+        Ok(())
+    }
+
+    pub fn end_trip(&mut self, car: &Car) -> Result<TripSummary, RentalError> {
+        if self.state == RentalState::InTrip {
+            if car.speed() != 0.0 {
+                return Err(RentalError::Moving);
+            }
+            if car.power_state() == PowerState::Ready {
+                return Err(RentalError::EngineRunning);
+            }
+        }
+        self.transition("end trip", RentalState::InTrip, RentalState::TripEnded)?;
+        let (distance, fuel_level) = self.start;
+        let summary = TripSummary {
+            distance: car.distance() - distance,
+            fuel_used: (fuel_level - car.fuel_level()).max(0.0),
+        };
+        self.summary = Some(summary);
+
+        Ok(summary)
+    }
+
+    pub fn lock(&mut self) -> Result<(), RentalError> {
+        self.transition("lock", RentalState::TripEnded, RentalState::Available)
+    }
+}
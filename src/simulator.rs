@@ -0,0 +1,615 @@
+use std::{
+    collections::VecDeque,
+    fmt, io,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Mutex, Weak},
+    task::{Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use futures_core::Stream;
+use tokio::{
+    sync::{broadcast, mpsc, oneshot, Notify},
+    time::{interval, Interval, MissedTickBehavior},
+};
+
+use crate::{
+    ActuatorLag, Car, CarConfig, Controller, Event, Reconfiguration, Telemetry, TelemetrySink,
+    TripSummary,
+};
+
+/// Sets the car's inputs before every update
+pub type Driver = Box<dyn FnMut(&mut Car) + Send>;
+
+/// Controls waiting to be handled before senders have to wait
+const CONTROL_CAPACITY: usize = 10;
+/// Telemetry records kept for slow subscribers before they start missing them
+const TELEMETRY_CAPACITY: usize = 64;
+/// Events kept for slow subscribers before they start missing them
+const EVENT_CAPACITY: usize = 256;
+/// Bounds of the wall-clock time between ticks whatever the real-time factor
+const MIN_PERIOD: Duration = Duration::from_nanos(1);
+const MAX_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+enum Control {
+    Pause,
+    Resume,
+    Step,
+    Telemetry(oneshot::Sender<Telemetry>),
+    Stats(oneshot::Sender<SimStats>),
+    /// one-off change to the car, applied between ticks
+    Command(Box<dyn FnOnce(&mut Car) + Send>),
+    Reconfigure(Box<CarConfig>),
+    SetTick(Duration),
+    SetDriver(Driver),
+    SetRealTimeFactor(f64),
+    Shutdown(oneshot::Sender<TripSummary>),
+}
+
+/// Controls a running [`Simulator`] from other tasks, e.g. a debugger or TUI.
+///
+/// Controls and commands go through a bounded queue, so senders wait when the simulator falls
+/// behind. Telemetry is broadcast and never holds the simulator back: a subscriber that falls
+/// more than 64 records behind skips ahead, and sees `RecvError::Lagged` saying how many it missed.
+#[derive(Clone)]
+pub struct SimulatorHandle {
+    tx: mpsc::Sender<Control>,
+    telemetry: broadcast::Sender<Telemetry>,
+    events: broadcast::Sender<Event>,
+    streams: Streams,
+}
+
+impl SimulatorHandle {
+    pub async fn pause(&self) {
+        _ = self.tx.send(Control::Pause).await;
+    }
+
+    pub async fn resume(&self) {
+        _ = self.tx.send(Control::Resume).await;
+    }
+
+    /// Advance a single tick, meant to be used while paused
+    pub async fn step_once(&self) {
+        _ = self.tx.send(Control::Step).await;
+    }
+
+    /// Current state of the car, `None` if the simulator has stopped
+    pub async fn telemetry(&self) -> Option<Telemetry> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send(Control::Telemetry(tx)).await.ok()?;
+        rx.await.ok()
+    }
+
+    /// `None` if the simulator has stopped
+    pub async fn stats(&self) -> Option<SimStats> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send(Control::Stats(tx)).await.ok()?;
+        rx.await.ok()
+    }
+
+    /// Change the car before the next tick, returns false if the simulator has stopped
+    pub async fn command(&self, command: impl FnOnce(&mut Car) + Send + 'static) -> bool {
+        self.tx.send(Control::Command(Box::new(command))).await.is_ok()
+    }
+
+    /// Replace the car's config between ticks, see [`Car::set_config`]. Returns false if the
+    /// simulator has stopped.
+    pub async fn reconfigure(&self, config: CarConfig) -> bool {
+        self.tx.send(Control::Reconfigure(Box::new(config))).await.is_ok()
+    }
+
+    /// Tick at a new rate from the next tick on, returns false for a zero tick or if the
+    /// simulator has stopped
+    pub async fn set_tick(&self, tick: Duration) -> bool {
+        if tick.is_zero() {
+            return false;
+        }
+        self.tx.send(Control::SetTick(tick)).await.is_ok()
+    }
+
+    /// Hand the car over to another driver, returns false if the simulator has stopped
+    pub async fn set_driver(&self, driver: impl FnMut(&mut Car) + Send + 'static) -> bool {
+        self.tx.send(Control::SetDriver(Box::new(driver))).await.is_ok()
+    }
+
+    /// Run faster or slower from the next tick on, see [`Simulator::with_real_time_factor`].
+    /// Returns false if the simulator has stopped.
+    pub async fn set_real_time_factor(&self, factor: f64) -> bool {
+        self.tx.send(Control::SetRealTimeFactor(factor)).await.is_ok()
+    }
+
+    /// Stop the simulator after flushing its sink, see [`Simulator::shutdown`]. `None` if it
+    /// had already stopped.
+    pub async fn shutdown(&self) -> Option<TripSummary> {
+        let (tx, rx) = oneshot::channel();
+        self.tx.send(Control::Shutdown(tx)).await.ok()?;
+        rx.await.ok()
+    }
+
+    /// Telemetry of every tick from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<Telemetry> {
+        self.telemetry.subscribe()
+    }
+
+    /// Events the car and the simulator raise from now on, published after every tick. Like
+    /// telemetry, a subscriber that falls more than 256 events behind skips ahead.
+    pub fn events(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    /// Telemetry of every tick from now on as a [`Stream`], for composing with stream
+    /// combinators. Like [`subscribe`](Self::subscribe), the oldest records are dropped if
+    /// the consumer falls more than 64 behind.
+    pub fn stream(&self) -> TelemetryStream {
+        self.stream_with(Backpressure::default(), TELEMETRY_CAPACITY)
+    }
+
+    /// Telemetry stream buffering up to `capacity` records, with `policy` deciding what
+    /// happens once the buffer is full
+    pub fn stream_with(&self, policy: Backpressure, capacity: usize) -> TelemetryStream {
+        let buffer = Arc::new(StreamBuffer {
+            policy,
+            capacity: capacity.max(1),
+            queue: Mutex::default(),
+            writable: Notify::new(),
+        });
+        self.streams.lock().unwrap().push(Arc::downgrade(&buffer));
+
+        TelemetryStream { buffer }
+    }
+}
+
+type Streams = Arc<Mutex<Vec<Weak<StreamBuffer>>>>;
+
+/// The simulator's side of the streams, ending them once it's dropped
+#[derive(Default)]
+struct OwnedStreams(Streams);
+
+impl Deref for OwnedStreams {
+    type Target = Streams;
+
+    fn deref(&self) -> &Streams {
+        &self.0
+    }
+}
+
+impl Drop for OwnedStreams {
+    fn drop(&mut self) {
+        for buffer in self.0.lock().unwrap().iter().filter_map(Weak::upgrade) {
+            buffer.close();
+        }
+    }
+}
+
+/// What a [`TelemetryStream`] does with new records while its consumer can't keep up, like
+/// a telematics unit on a congested uplink
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backpressure {
+    /// make room by dropping the oldest buffered record, the consumer catches up on the
+    /// latest state
+    #[default]
+    DropOldest,
+    /// drop new records until there's room, the consumer sees an unbroken run of the past
+    DropNewest,
+    /// hold the simulator back until there's room, nothing is lost but ticks get fewer and
+    /// longer, and controls wait too
+    Block,
+}
+
+#[derive(Default)]
+struct Queue {
+    records: VecDeque<Telemetry>,
+    dropped: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+struct StreamBuffer {
+    policy: Backpressure,
+    capacity: usize,
+    queue: Mutex<Queue>,
+    /// a record was taken out of the buffer
+    writable: Notify,
+}
+
+impl StreamBuffer {
+    fn push(&self, record: Telemetry) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.records.len() >= self.capacity {
+            match self.policy {
+                Backpressure::DropOldest => {
+                    queue.records.pop_front();
+                    queue.dropped += 1;
+                }
+                Backpressure::DropNewest => {
+                    queue.dropped += 1;
+                    return;
+                }
+                // The simulator waits for room before stepping, only `step_once` gets here
+                Backpressure::Block => {}
+            }
+        }
+        queue.records.push_back(record);
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.queue.lock().unwrap().records.len() >= self.capacity
+    }
+
+    fn close(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.closed = true;
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Telemetry of a running [`Simulator`], ends when the simulator is dropped
+pub struct TelemetryStream {
+    buffer: Arc<StreamBuffer>,
+}
+
+impl TelemetryStream {
+    /// Records lost to backpressure so far
+    pub fn dropped(&self) -> u64 {
+        self.buffer.queue.lock().unwrap().dropped
+    }
+}
+
+impl Stream for TelemetryStream {
+    type Item = Telemetry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Telemetry>> {
+        let mut queue = self.buffer.queue.lock().unwrap();
+        if let Some(record) = queue.records.pop_front() {
+            self.buffer.writable.notify_one();
+            return Poll::Ready(Some(record));
+        }
+        if queue.closed {
+            return Poll::Ready(None);
+        }
+        queue.waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+/// How well the simulator keeps up with its tick rate
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SimStats {
+    pub ticks: u64,
+    /// time spent in the last tick updating the car and sending its telemetry
+    pub last_compute: Duration,
+    pub max_compute: Duration,
+    pub total_compute: Duration,
+    /// ticks the last tick had to catch up on, anything above 0 means it can't keep up
+    pub ticks_behind: u64,
+}
+
+impl SimStats {
+    /// Ticks that could be computed per second of wall time, if nothing else ran
+    pub fn throughput(&self) -> f64 {
+        if self.total_compute.is_zero() {
+            return 0.0;
+        }
+
+        self.ticks as f64 / self.total_compute.as_secs_f64()
+    }
+}
+
+/// Simulator tick of zero, the simulator could never wait between ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroTick;
+
+impl fmt::Display for ZeroTick {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "simulator tick must be longer than zero")
+    }
+}
+
+impl std::error::Error for ZeroTick {}
+
+/// Drives a car in real time: every tick the driver adjusts inputs, the car is updated
+/// and its telemetry is sent to the sink
+pub struct Simulator<S> {
+    car: Car,
+    sink: S,
+    tick: Duration,
+    real_time_factor: f64,
+    driver: Option<Driver>,
+    controller: Option<Box<dyn Controller>>,
+    actuator_lag: Option<ActuatorLag>,
+    paused: bool,
+    stats: SimStats,
+    control_tx: mpsc::Sender<Control>,
+    control_rx: mpsc::Receiver<Control>,
+    telemetry_tx: broadcast::Sender<Telemetry>,
+    event_tx: broadcast::Sender<Event>,
+    streams: OwnedStreams,
+    /// odometer and fuel level when the simulator was created
+    start: (f64, f64),
+}
+
+impl<S: TelemetrySink> Simulator<S> {
+    /// Panics on a zero tick, see [`Simulator::try_new`]
+    pub fn new(car: Car, sink: S, tick: Duration) -> Self {
+        Self::try_new(car, sink, tick).expect("tick is longer than zero")
+    }
+
+    /// Like [`Simulator::new`], but rejects a zero tick
+    pub fn try_new(car: Car, sink: S, tick: Duration) -> Result<Self, ZeroTick> {
+        if tick.is_zero() {
+            return Err(ZeroTick);
+        }
+        let (control_tx, control_rx) = mpsc::channel(CONTROL_CAPACITY);
+        let (telemetry_tx, _) = broadcast::channel(TELEMETRY_CAPACITY);
+        let (event_tx, _) = broadcast::channel(EVENT_CAPACITY);
+
+        Ok(Self {
+            start: (car.distance(), car.fuel_level()),
+            car,
+            sink,
+            tick,
+            real_time_factor: 1.0,
+            driver: None,
+            controller: None,
+            actuator_lag: None,
+            paused: false,
+            stats: SimStats::default(),
+            control_tx,
+            control_rx,
+            telemetry_tx,
+            event_tx,
+            streams: OwnedStreams::default(),
+        })
+    }
+
+    pub fn with_driver(mut self, driver: impl FnMut(&mut Car) + Send + 'static) -> Self {
+        self.driver = Some(Box::new(driver));
+        self
+    }
+
+    /// Closes the loop with an external controller: every tick it gets the telemetry of the
+    /// last one and its actuation is applied before the car is updated, or later with
+    /// [`Simulator::with_actuator_lag`]. An invalid actuation
+    /// stops the simulator with the [`ControlError`](crate::ControlError).
+    pub fn with_controller(mut self, controller: impl Controller + 'static) -> Self {
+        self.controller = Some(Box::new(controller));
+        self
+    }
+
+    /// Delay the controller's actuations instead of applying them on the tick they're commanded
+    pub fn with_actuator_lag(mut self, lag: ActuatorLag) -> Self {
+        self.actuator_lag = Some(lag);
+        self
+    }
+
+    /// Tick `factor` times as often, e.g. 0.5 for slow motion in a demo or 100.0 for a batch
+    /// run, without changing the simulated time each tick covers. Factors that aren't positive
+    /// are ignored.
+    pub fn with_real_time_factor(mut self, factor: f64) -> Self {
+        self.set_real_time_factor(factor);
+        self
+    }
+
+    fn set_real_time_factor(&mut self, factor: f64) -> bool {
+        if !(factor.is_finite() && factor > 0.0) {
+            return false;
+        }
+        self.real_time_factor = factor;
+
+        true
+    }
+
+    /// Wall-clock time between ticks, kept within a nanosecond and a day so extreme factors
+    /// neither round it to zero nor overflow it
+    fn period(&self) -> Duration {
+        Duration::try_from_secs_f64(self.tick.as_secs_f64() / self.real_time_factor)
+            .unwrap_or(MAX_PERIOD)
+            .clamp(MIN_PERIOD, MAX_PERIOD)
+    }
+
+    pub fn handle(&self) -> SimulatorHandle {
+        SimulatorHandle {
+            tx: self.control_tx.clone(),
+            telemetry: self.telemetry_tx.clone(),
+            events: self.event_tx.clone(),
+            streams: Streams::clone(&self.streams),
+        }
+    }
+
+    pub fn car(&self) -> &Car {
+        &self.car
+    }
+
+    pub fn car_mut(&mut self) -> &mut Car {
+        &mut self.car
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn stats(&self) -> SimStats {
+        self.stats
+    }
+
+    pub fn step_once(&mut self) -> io::Result<()> {
+        self.step(1.0)
+    }
+
+    /// Stop driving, flush the sink and report the trip driven since the simulator was created,
+    /// handing the sink back to read what it buffered or checked. Streams end here.
+    pub fn shutdown(mut self) -> io::Result<(TripSummary, S)> {
+        let summary = self.finish()?;
+
+        Ok((summary, self.sink))
+    }
+
+    fn finish(&mut self) -> io::Result<TripSummary> {
+        self.driver = None;
+        self.controller = None;
+        self.sink.flush()?;
+
+        let (distance, fuel_level) = self.start;
+        Ok(TripSummary {
+            distance: self.car.distance() - distance,
+            fuel_used: fuel_level - self.car.fuel_level(),
+        })
+    }
+
+    /// Update the car over `ticks` ticks worth of time in one go
+    fn step(&mut self, ticks: f64) -> io::Result<()> {
+        let start = Instant::now();
+        if let Some(driver) = &mut self.driver {
+            driver(&mut self.car);
+        }
+        if let Some(controller) = &mut self.controller {
+            let actuation = controller.control(&self.car.telemetry());
+            match &mut self.actuator_lag {
+                Some(lag) => lag.command(actuation),
+                None => actuation.apply(&mut self.car).map_err(io::Error::other)?,
+            }
+        }
+        if let Some(lag) = &mut self.actuator_lag {
+            while let Some(actuation) = lag.next_due() {
+                actuation.apply(&mut self.car).map_err(io::Error::other)?;
+            }
+        }
+        let missed = (ticks - 1.0).round() as u64;
+        if missed > 0 {
+            self.car.emit(Event::TicksMissed { missed });
+        }
+        self.stats.ticks_behind = missed;
+        let time_step = self.car.config().time_step * ticks;
+        self.car.update_by(time_step);
+        if let Some(lag) = &mut self.actuator_lag {
+            lag.advance(time_step);
+        }
+        let telemetry = self.car.telemetry();
+        self.publish_events();
+        // Nobody subscribed is fine
+        _ = self.telemetry_tx.send(telemetry.clone());
+        self.streams.lock().unwrap().retain(|buffer| match buffer.upgrade() {
+            Some(buffer) => {
+                buffer.push(telemetry.clone());
+                true
+            }
+            // The stream was dropped
+            None => false,
+        });
+        let result = self.sink.send(&telemetry);
+
+        let compute = start.elapsed();
+        self.stats.ticks += 1;
+        self.stats.last_compute = compute;
+        self.stats.max_compute = self.stats.max_compute.max(compute);
+        self.stats.total_compute += compute;
+
+        result
+    }
+
+    /// Ticks until the sink fails or the simulator is shut down through a handle, ticks are
+    /// skipped while paused. Missed ticks are made up for by stepping the car over the actual
+    /// time elapsed, so it never runs slower than real time.
+    pub async fn run(mut self) -> io::Result<()> {
+        let mut ticker = ticker_every(self.period());
+        let mut last_tick: Option<Instant> = None;
+
+        loop {
+            tokio::select! {
+                _ = ticker.tick(), if !self.paused => {
+                    wait_for_room(&self.streams).await;
+                    let now = Instant::now();
+                    let ticks = match last_tick {
+                        Some(last) => now.duration_since(last).as_secs_f64() / self.period().as_secs_f64(),
+                        None => 1.0,
+                    };
+                    last_tick = Some(now);
+                    // Timer jitter isn't worth catching up on
+                    self.step(if ticks < 1.5 { 1.0 } else { ticks })?
+                }
+                Some(control) = self.control_rx.recv() => match control {
+                    Control::Pause => self.pause(),
+                    Control::Resume => {
+                        self.resume();
+                        ticker.reset();
+                        // Time spent paused isn't missed
+                        last_tick = None;
+                    }
+                    Control::Step => self.step_once()?,
+                    Control::Telemetry(tx) => _ = tx.send(self.car.telemetry()),
+                    Control::Stats(tx) => _ = tx.send(self.stats),
+                    Control::Command(command) => command(&mut self.car),
+                    Control::Reconfigure(config) => self.car.set_config(*config),
+                    Control::SetTick(tick) => {
+                        self.tick = tick;
+                        ticker = ticker_every(self.period());
+                        last_tick = None;
+                        self.car.emit(Event::Reconfigured(Reconfiguration::Tick(tick)));
+                    }
+                    Control::SetRealTimeFactor(factor) => {
+                        if self.set_real_time_factor(factor) {
+                            ticker = ticker_every(self.period());
+                            last_tick = None;
+                            self.car.emit(Event::Reconfigured(Reconfiguration::RealTimeFactor(factor)));
+                        }
+                    }
+                    Control::SetDriver(driver) => {
+                        self.driver = Some(driver);
+                        self.car.emit(Event::Reconfigured(Reconfiguration::Driver));
+                    }
+                    Control::Shutdown(tx) => {
+                        _ = tx.send(self.finish()?);
+                        return Ok(());
+                    }
+                },
+            }
+            // Reconfigurations while paused are published right away rather than on resume
+            self.publish_events();
+        }
+    }
+
+    /// Hand the car's events to subscribers, drained even if nobody listens so they don't pile up
+    fn publish_events(&mut self) {
+        for event in self.car.drain_events() {
+            _ = self.event_tx.send(event);
+        }
+    }
+}
+
+fn ticker_every(period: Duration) -> Interval {
+    let mut ticker = interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    ticker
+}
+
+/// Wait for every stream with [`Backpressure::Block`] to have room for another record
+async fn wait_for_room(streams: &Streams) {
+    loop {
+        let full = streams.lock().unwrap().iter().find_map(|buffer| {
+            buffer
+                .upgrade()
+                .filter(|buffer| buffer.policy == Backpressure::Block && buffer.is_full())
+        });
+        match full {
+            // notify_one leaves a permit if nobody is waiting, so a record taken out since
+            // checking isn't missed
+            Some(buffer) => buffer.writable.notified().await,
+            None => break,
+        }
+    }
+}
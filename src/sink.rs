@@ -0,0 +1,226 @@
+use std::io::{self, Write};
+
+use serde_json::{Map, Value};
+
+use crate::Telemetry;
+
+/// Destination for telemetry emitted by the simulation
+pub trait TelemetrySink {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()>;
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Lends a sink to a [`Simulator`](crate::Simulator) or a wrapping sink, so its state can be read
+/// once the run is over
+impl<S: TelemetrySink + ?Sized> TelemetrySink for &mut S {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        (**self).send(telemetry)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+}
+
+/// Keeps everything in memory
+impl TelemetrySink for Vec<Telemetry> {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        self.push(telemetry.clone());
+        Ok(())
+    }
+}
+
+/// Wire format of a telemetry record
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    #[cfg(feature = "cbor")]
+    Cbor,
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+}
+
+impl Encoding {
+    /// A single record, see [`WriterSink`] for how records are delimited in a stream
+    pub fn encode(&self, telemetry: &Telemetry) -> io::Result<Vec<u8>> {
+        match self {
+            Encoding::Json => Ok(serde_json::to_vec(telemetry)?),
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => {
+                let mut buf = vec![];
+                ciborium::into_writer(telemetry, &mut buf)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                Ok(buf)
+            }
+            #[cfg(feature = "protobuf")]
+            Encoding::Protobuf => {
+                Ok(prost::Message::encode_to_vec(&crate::proto::Telemetry::from(telemetry)))
+            }
+        }
+    }
+}
+
+/// Encodes telemetry onto any writer, e.g. a file or a TCP stream. JSON records are newline
+/// delimited, CBOR is self-delimiting and protobuf messages are prefixed with a varint length.
+pub struct WriterSink<W> {
+    writer: W,
+    encoding: Encoding,
+    /// total bytes written, handy when comparing encodings for constrained uplinks
+    bytes_written: usize,
+}
+
+impl<W: Write> WriterSink<W> {
+    pub fn new(writer: W, encoding: Encoding) -> Self {
+        Self { writer, encoding, bytes_written: 0 }
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> TelemetrySink for WriterSink<W> {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        let mut buf = self.encoding.encode(telemetry)?;
+        match self.encoding {
+            Encoding::Json => buf.push(b'\n'),
+            #[cfg(feature = "cbor")]
+            Encoding::Cbor => {}
+            #[cfg(feature = "protobuf")]
+            Encoding::Protobuf => {
+                let mut framed = vec![];
+                prost::encoding::encode_varint(buf.len() as u64, &mut framed);
+                framed.append(&mut buf);
+                buf = framed;
+            }
+        }
+        self.writer.write_all(&buf)?;
+        self.bytes_written += buf.len();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Frames each record with its length as a big-endian u32, the way an on-vehicle gateway
+/// feeds an edge agent over a serial line or local socket
+pub struct FramedSink<W> {
+    writer: W,
+    encoding: Encoding,
+}
+
+impl<W: Write> FramedSink<W> {
+    pub fn new(writer: W, encoding: Encoding) -> Self {
+        Self { writer, encoding }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(unix)]
+impl FramedSink<std::os::unix::net::UnixStream> {
+    pub fn connect_unix(path: impl AsRef<std::path::Path>, encoding: Encoding) -> io::Result<Self> {
+        Ok(Self::new(std::os::unix::net::UnixStream::connect(path)?, encoding))
+    }
+}
+
+impl FramedSink<std::fs::File> {
+    /// Line settings (baud rate etc.) are left as they are, set them up beforehand, e.g. with `stty`
+    pub fn open_serial(path: impl AsRef<std::path::Path>, encoding: Encoding) -> io::Result<Self> {
+        let port = std::fs::OpenOptions::new().write(true).open(path)?;
+        Ok(Self::new(port, encoding))
+    }
+}
+
+impl<W: Write> TelemetrySink for FramedSink<W> {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        let buf = self.encoding.encode(telemetry)?;
+        let len = u32::try_from(buf.len()).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidInput, "record too large to frame")
+        })?;
+        self.writer.write_all(&len.to_be_bytes())?;
+        self.writer.write_all(&buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Newline delimited JSON carrying only the fields that changed since the previous record, in
+/// the delta style of TCU protocols. Every `keyframe_interval` records, and first of all, a
+/// full record marked with `"keyframe": true` is sent instead, so consumers can join mid-stream.
+/// A field that disappeared, e.g. an `Option` becoming `None`, is sent as `null`.
+pub struct DeltaSink<W> {
+    writer: W,
+    keyframe_interval: usize,
+    last: Option<Map<String, Value>>,
+    since_keyframe: usize,
+    bytes_written: usize,
+}
+
+impl<W: Write> DeltaSink<W> {
+    pub fn new(writer: W, keyframe_interval: usize) -> Self {
+        Self { writer, keyframe_interval, last: None, since_keyframe: 0, bytes_written: 0 }
+    }
+
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> TelemetrySink for DeltaSink<W> {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        let Value::Object(record) = serde_json::to_value(telemetry)? else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "record isn't an object"));
+        };
+
+        let report = match &self.last {
+            Some(last) if self.since_keyframe < self.keyframe_interval => {
+                self.since_keyframe += 1;
+                let mut delta: Map<String, Value> = record
+                    .iter()
+                    .filter(|(field, value)| last.get(*field) != Some(value))
+                    .map(|(field, value)| (field.clone(), value.clone()))
+                    .collect();
+                for field in last.keys().filter(|field| !record.contains_key(*field)) {
+                    delta.insert(field.clone(), Value::Null);
+                }
+                delta
+            }
+            _ => {
+                self.since_keyframe = 1;
+                let mut keyframe = record.clone();
+                keyframe.insert("keyframe".to_owned(), Value::Bool(true));
+                keyframe
+            }
+        };
+        self.last = Some(record);
+
+        let mut buf = serde_json::to_vec(&report)?;
+        buf.push(b'\n');
+        self.writer.write_all(&buf)?;
+        self.bytes_written += buf.len();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
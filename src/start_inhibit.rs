@@ -0,0 +1,35 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Conditions a start has to meet, none by default
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StartInhibitors {
+    /// brake pedal pressed while turning the key
+    pub brake_to_start: bool,
+    /// one of the registered keys in use, see [`Car::register_key`](crate::Car::register_key)
+    pub immobilizer: bool,
+    /// hood closed
+    pub hood_closed: bool,
+}
+
+/// Why the car refused to start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum StartInhibit {
+    BrakeNotPressed,
+    /// no registered key in use
+    Immobilized,
+    HoodOpen,
+}
+
+impl fmt::Display for StartInhibit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StartInhibit::BrakeNotPressed => write!(f, "press the brake to start"),
+            StartInhibit::Immobilized => write!(f, "immobilizer didn't recognise the key"),
+            StartInhibit::HoodOpen => write!(f, "hood is open"),
+        }
+    }
+}
+
+impl std::error::Error for StartInhibit {}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// Road surface under the car, changes how far it coasts, how hard it can launch and how much
+/// it shakes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Surface {
+    #[default]
+    Asphalt,
+    Cobblestone,
+    Gravel,
+    OffRoad,
+}
+
+impl Surface {
+    /// Rolling resistance coefficient, resistance force per unit of weight
+    pub fn rolling_resistance(&self) -> f64 {
+        match self {
+            Surface::Asphalt => 0.013,
+            Surface::Cobblestone => 0.02,
+            Surface::Gravel => 0.03,
+            Surface::OffRoad => 0.08,
+        }
+    }
+
+    /// Tire friction coefficient, acceleration is limited to this times g
+    pub fn grip(&self) -> f64 {
+        match self {
+            Surface::Asphalt => 0.9,
+            Surface::Cobblestone => 0.7,
+            Surface::Gravel => 0.6,
+            Surface::OffRoad => 0.5,
+        }
+    }
+
+    /// Road vibration relative to asphalt
+    pub fn roughness(&self) -> f64 {
+        match self {
+            Surface::Asphalt => 1.0,
+            Surface::Cobblestone => 3.0,
+            Surface::Gravel => 2.5,
+            Surface::OffRoad => 4.0,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// A break at least this long (in s) resets continuous driving time
+const QUALIFYING_BREAK: f64 = 45.0 * 60.0;
+
+/// Driving and rest time record, as kept by heavy vehicle tachographs. All times in seconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Tachograph {
+    /// driving since the last qualifying break
+    pub continuous_driving: f64,
+    pub total_driving: f64,
+    /// length of the current stop, 0.0 while driving
+    pub current_rest: f64,
+    pub total_rest: f64,
+}
+
+impl Tachograph {
+    pub(crate) fn update(&mut self, moving: bool, time_step: f64) {
+        if moving {
+            self.continuous_driving += time_step;
+            self.total_driving += time_step;
+            self.current_rest = 0.0;
+        } else {
+            self.current_rest += time_step;
+            self.total_rest += time_step;
+            if self.current_rest >= QUALIFYING_BREAK {
+                self.continuous_driving = 0.0;
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::defaults::AMBIENT_TEMPERATURE;
+use crate::{
+    Counters, DriveMode, Efficiency, Gear, HandBrake, ParkingSensor, PowerState, Tachograph, Units,
+    WheelSpeeds,
+};
+
+/// Version of the telemetry format, bumped whenever records of the previous version can't be
+/// read as they are. Fields added since default when missing, so they don't need a bump.
+pub const TELEMETRY_VERSION: u32 = 2;
+
+/// Snapshot of the car's state after an update
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Telemetry {
+    /// see [`TELEMETRY_VERSION`]
+    pub version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vin: Option<String>,
+    /// kmph or mph depending on `units`
+    pub speed: f64,
+    /// odometer in km or miles depending on `units`
+    pub distance: f64,
+    pub units: Units,
+    pub rpm: u32,
+    pub gear: Gear,
+    /// share of the tank
+    #[cfg_attr(feature = "schema", schemars(range(min = 0.0, max = 1.0)))]
+    pub fuel_level: f64,
+    #[cfg_attr(feature = "schema", schemars(range(min = 0.0, max = 1.0)))]
+    pub accelerator_position: f64,
+    #[cfg_attr(feature = "schema", schemars(range(min = 0.0, max = 1.0)))]
+    pub brake_position: f64,
+    #[cfg_attr(feature = "schema", schemars(range(min = 0.0, max = 1.0)))]
+    pub clutch_position: f64,
+    pub hand_brake: HandBrake,
+    #[cfg_attr(feature = "schema", schemars(range(min = 0.0, max = 1.0)))]
+    pub hand_brake_travel: f64,
+    pub drive_mode: DriveMode,
+    pub power_state: PowerState,
+    /// in bar, for vehicles with air brakes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub air_pressure: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tachograph: Option<Tachograph>,
+    /// in °C
+    pub rear_brake_temperature: f64,
+    pub aeb_active: bool,
+    /// power and speed are limited after a critical fault
+    #[serde(default)]
+    pub limp_mode: bool,
+    /// NVH proxy, RMS acceleration in m/s²
+    pub vibration: f64,
+    /// profile bound to the key in use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub driver: Option<String>,
+    /// while reversing towards an obstacle in range
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parking_sensor: Option<ParkingSensor>,
+    /// above sea level in m
+    #[serde(default)]
+    pub altitude: f64,
+    /// while driving
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
+    #[serde(default)]
+    pub counters: Counters,
+    #[serde(default)]
+    pub brake_light: bool,
+    #[serde(default)]
+    pub reverse_light: bool,
+    /// engine running and ready to drive
+    #[serde(default)]
+    pub ready_lamp: bool,
+    /// emergency vehicle responding, exempt from speed limits
+    #[serde(default)]
+    pub sirens: bool,
+    /// same units as `speed`
+    #[serde(default)]
+    pub wheel_speeds: WheelSpeeds,
+    /// acceleration was limited by tire grip rather than engine power
+    #[serde(default)]
+    pub traction_limited: bool,
+    /// electrical load of the heater and other accessories in kW
+    #[serde(default)]
+    pub accessory_load: f64,
+    /// in °C
+    #[serde(default)]
+    pub cabin_temperature: f64,
+}
+
+/// Bounds of what a car's telemetry can report, in the same units, for scaling gauges and
+/// bound-checking streams
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Limits {
+    pub units: Units,
+    /// top speed in the current drive mode, capped by the speed limiter
+    pub max_speed: f64,
+    pub max_rpm: u32,
+    /// in kW, in the current drive mode
+    pub max_power: f64,
+}
+
+impl Telemetry {
+    /// Read a recorded JSON record of this or any earlier format version, filling in what
+    /// older versions didn't report
+    pub fn migrate(mut record: Value) -> Result<Telemetry, serde_json::Error> {
+        let version = record.get("version").and_then(Value::as_u64).unwrap_or(1);
+        if version < 2 {
+            upgrade_v1(&mut record);
+        }
+
+        serde_json::from_value(record)
+    }
+}
+
+// Version 1 had no version field and grew a field at a time, so fill in whichever are missing
+fn upgrade_v1(record: &mut Value) {
+    let Some(fields) = record.as_object_mut() else { return };
+    let hand_brake: HandBrake = fields
+        .get("hand_brake")
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+    let defaults = [
+        ("distance", json!(0.0)),
+        ("units", json!(Units::Metric)),
+        // Engine was always running before power states
+        ("power_state", json!(PowerState::Ready)),
+        ("rear_brake_temperature", json!(AMBIENT_TEMPERATURE)),
+        ("aeb_active", json!(false)),
+        ("vibration", json!(0.0)),
+        ("hand_brake_travel", json!(hand_brake.travel())),
+    ];
+    for (field, default) in defaults {
+        fields.entry(field).or_insert(default);
+    }
+    fields.insert("version".to_owned(), json!(2));
+}
+
+#[cfg(feature = "schema")]
+impl Telemetry {
+    /// JSON Schema of the JSON encoding, field descriptions carry the units
+    pub fn schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Telemetry)).expect("schema is valid JSON")
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl Telemetry {
+    /// Encode as the `Telemetry` message in `proto/telemetry.proto`
+    pub fn to_protobuf(&self) -> Vec<u8> {
+        prost::Message::encode_to_vec(&crate::proto::Telemetry::from(self))
+    }
+}
@@ -0,0 +1,111 @@
+use std::io;
+
+use serde::Serialize;
+
+use crate::{Telemetry, TelemetrySink, Units, KM_PER_MILE};
+
+/// In m/s², beyond what tyres on a road can accelerate or brake a car at
+const MAX_ACCELERATION: f64 = 12.0;
+
+/// Change between consecutive records, or a value, that no car could produce
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Violation {
+    /// speed changed faster than the acceleration limit allows, in m/s²
+    SpeedJump { acceleration: f64 },
+    /// odometer went backwards, in km
+    DistanceDecreased { by: f64 },
+    /// fuel level went up while moving, as a share of the tank
+    FuelRoseWhileDriving { by: f64 },
+    /// share of the tank or pedal travel outside 0.0..=1.0
+    OutOfRange { field: &'static str, value: f64 },
+}
+
+/// Flags physically impossible telemetry, from the simulator as a self-check or from recorded
+/// fleet data. Unlike [`Assertions`](crate::Assertions) it never fails as a sink, violations
+/// are collected for inspection.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    /// seconds between records
+    pub time_step: f64,
+    /// in m/s²
+    pub max_acceleration: f64,
+    last: Option<Telemetry>,
+    updates: usize,
+    violations: Vec<(usize, Violation)>,
+}
+
+impl Validator {
+    pub fn new(time_step: f64) -> Self {
+        Self {
+            time_step,
+            max_acceleration: MAX_ACCELERATION,
+            last: None,
+            updates: 0,
+            violations: vec![],
+        }
+    }
+
+    /// Violations of a record, against the one before it
+    pub fn check(&mut self, telemetry: &Telemetry) -> Vec<Violation> {
+        self.updates += 1;
+        let mut violations = vec![];
+        let ranges = [
+            ("fuel_level", telemetry.fuel_level),
+            ("accelerator_position", telemetry.accelerator_position),
+            ("brake_position", telemetry.brake_position),
+            ("clutch_position", telemetry.clutch_position),
+        ];
+        for (field, value) in ranges {
+            if !(0.0..=1.0).contains(&value) {
+                violations.push(Violation::OutOfRange { field, value });
+            }
+        }
+
+        if let Some(last) = &self.last {
+            let acceleration =
+                (kmph(telemetry) - kmph(last)).abs() / 3.6 / self.time_step.max(f64::EPSILON);
+            if acceleration > self.max_acceleration {
+                violations.push(Violation::SpeedJump { acceleration });
+            }
+            let distance = km(telemetry) - km(last);
+            if distance < 0.0 {
+                violations.push(Violation::DistanceDecreased { by: -distance });
+            }
+            let refuelled = telemetry.fuel_level - last.fuel_level;
+            if refuelled > 0.0 && telemetry.speed != 0.0 && last.speed != 0.0 {
+                violations.push(Violation::FuelRoseWhileDriving { by: refuelled });
+            }
+        }
+        self.last = Some(telemetry.clone());
+
+        self.violations
+            .extend(violations.iter().map(|violation| (self.updates, violation.clone())));
+        violations
+    }
+
+    /// Every violation so far, with the update it was found at
+    pub fn violations(&self) -> &[(usize, Violation)] {
+        &self.violations
+    }
+}
+
+impl TelemetrySink for Validator {
+    fn send(&mut self, telemetry: &Telemetry) -> io::Result<()> {
+        self.check(telemetry);
+        Ok(())
+    }
+}
+
+fn kmph(telemetry: &Telemetry) -> f64 {
+    match telemetry.units {
+        Units::Metric => telemetry.speed,
+        Units::Imperial => telemetry.speed * KM_PER_MILE,
+    }
+}
+
+fn km(telemetry: &Telemetry) -> f64 {
+    match telemetry.units {
+        Units::Metric => telemetry.distance,
+        Units::Imperial => telemetry.distance * KM_PER_MILE,
+    }
+}
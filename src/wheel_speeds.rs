@@ -0,0 +1,43 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "rand")]
+use crate::Distribution;
+
+/// Wheel speed sensor readings, in kmph or mph like the vehicle speed. Every wheel turns at the
+/// vehicle speed but for slip: the driven front wheels run ahead while accelerating, braked wheels
+/// fall behind and lock up once the brakes ask for more than the tires grip, and the hand brake
+/// slows the rear ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct WheelSpeeds {
+    pub front_left: f64,
+    pub front_right: f64,
+    pub rear_left: f64,
+    pub rear_right: f64,
+}
+
+impl WheelSpeeds {
+    #[cfg(feature = "rand")]
+    /// Readings with sensor noise of `std_dev` added to each channel
+    pub fn with_noise(self, rng: &mut impl Rng, std_dev: f64) -> Self {
+        let noise = Distribution::Normal { mean: 0.0, std_dev };
+        let mut noisy = |speed: f64| speed + rng.sample(noise);
+        Self {
+            front_left: noisy(self.front_left),
+            front_right: noisy(self.front_right),
+            rear_left: noisy(self.rear_left),
+            rear_right: noisy(self.rear_right),
+        }
+    }
+
+    pub(crate) fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        Self {
+            front_left: f(self.front_left),
+            front_right: f(self.front_right),
+            rear_left: f(self.rear_left),
+            rear_right: f(self.rear_right),
+        }
+    }
+}
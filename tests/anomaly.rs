@@ -0,0 +1,36 @@
+#![cfg(feature = "rand")]
+
+use rand::{rngs::StdRng, SeedableRng};
+use vehicle_dynamics::{Anomalies, AnomalySink, Car, TelemetrySink, KM_PER_MILE};
+
+// Records stuck while sending a short drive through the sink
+fn stuck(anomalies: Anomalies) -> usize {
+    let mut sink = AnomalySink::new(vec![], anomalies, StdRng::seed_from_u64(0));
+    let mut car = Car::new(0.5);
+    for _ in 0..10 {
+        car.update();
+        sink.send(&car.telemetry()).unwrap();
+    }
+    sink.injected().stuck
+}
+
+#[test]
+fn stuck_for_no_records_never_sticks() {
+    assert_eq!(stuck(Anomalies { stuck: 1.0, stuck_for: 0, ..Default::default() }), 0);
+    assert_eq!(stuck(Anomalies { stuck: 1.0, stuck_for: 3, ..Default::default() }), 10);
+}
+
+#[test]
+fn unit_flip_reports_mph() {
+    let mut car = Car::new(0.5);
+    car.update();
+    let mut telemetry = car.telemetry();
+    telemetry.speed = 100.0;
+    let mut sink = AnomalySink::new(
+        vec![],
+        Anomalies { unit_flip: 1.0, ..Default::default() },
+        StdRng::seed_from_u64(0),
+    );
+    sink.send(&telemetry).unwrap();
+    assert_eq!(sink.into_inner()[0].speed, 100.0 / KM_PER_MILE);
+}
@@ -0,0 +1,28 @@
+use vehicle_dynamics::{Cadence, Car, CarConfig, SignalGroup};
+
+// Seconds between reports of a group that only reports on its interval
+fn reports(time_step: f64) -> Vec<f64> {
+    let mut car = Car::with_config(0.5, CarConfig { time_step, ..CarConfig::default() });
+    let mut cadence = Cadence::new(vec![SignalGroup::new("fuel", &["fuel_level"], 5.0)]);
+    let mut reported = vec![];
+    for update in 1..=(20.0 / time_step).round() as u32 {
+        car.update();
+        if cadence.report(&car.telemetry(), time_step).is_some() {
+            reported.push(update as f64 * time_step);
+        }
+    }
+    reported
+}
+
+#[test]
+fn interval_is_in_seconds_at_any_time_step() {
+    for time_step in [1.0, 0.1] {
+        let reported = reports(time_step);
+        assert_eq!(reported.len(), 4, "{reported:?} at a {time_step} s step");
+        // First report goes out with the first update
+        for (reported, expected) in reported.iter().zip([0.0, 5.0, 10.0, 15.0]) {
+            let expected = expected + time_step;
+            assert!((reported - expected).abs() < 1e-6, "{reported} at a {time_step} s step");
+        }
+    }
+}
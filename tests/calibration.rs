@@ -0,0 +1,47 @@
+use vehicle_dynamics::{
+    calibrate, trace_error, Car, CarConfig, Gear, HandBrake, InitialState, Parameter, TraceSample,
+};
+
+fn initial() -> InitialState {
+    InitialState {
+        speed: 100.0,
+        gear: Gear::Neutral,
+        hand_brake: HandBrake::Disengaged,
+        ..Default::default()
+    }
+}
+
+// Brake for a few seconds then coast in neutral, recording the speeds `config` gives
+fn record(config: &CarConfig) -> Vec<TraceSample> {
+    let mut trace: Vec<_> = (0..60)
+        .map(|update| TraceSample {
+            accelerator: 0.0,
+            brake: if update < 5 { 0.3 } else { 0.0 },
+            clutch: 0.0,
+            gear: Gear::Neutral,
+            speed: 0.0,
+        })
+        .collect();
+    let mut car = Car::warm_start(InitialState { running: true, ..initial() }, config.clone());
+    for sample in &mut trace {
+        car.set_brake_position(sample.brake);
+        car.update();
+        sample.speed = car.speed();
+    }
+    trace
+}
+
+#[test]
+fn replay_releases_the_brake() {
+    let config = CarConfig::default();
+    let trace = record(&config);
+    assert!(trace_error(&trace, &initial(), &config) < 1e-12);
+}
+
+#[test]
+fn fit_recovers_the_drag_area() {
+    let actual = CarConfig { drag_area: 0.8, ..CarConfig::default() };
+    let trace = record(&actual);
+    let fit = calibrate(&trace, &initial(), CarConfig::default(), &[Parameter::DragArea]);
+    assert!((fit.drag_area - actual.drag_area).abs() < 0.01, "drag area {}", fit.drag_area);
+}
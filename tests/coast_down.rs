@@ -0,0 +1,19 @@
+use vehicle_dynamics::{coast_down, CarConfig, Surface};
+
+#[test]
+fn fit_recovers_the_default_road_load() {
+    for time_step in [1.0, 0.1] {
+        let config = CarConfig { time_step, ..CarConfig::default() };
+        let fit = coast_down(config.clone(), Surface::Asphalt).expect("car slows down");
+        assert!(
+            (fit.rolling_resistance - Surface::Asphalt.rolling_resistance()).abs() < 1e-3,
+            "rolling resistance {} at a {time_step} s step",
+            fit.rolling_resistance
+        );
+        assert!(
+            (fit.drag_area - config.drag_area).abs() < 0.01,
+            "drag area {} at a {time_step} s step",
+            fit.drag_area
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use vehicle_dynamics::{Car, CarConfig, DriveMode, Gear, HandBrake, InitialState};
+
+// Speed after holding the accelerator down in fourth for a while
+fn flat_out(mode: DriveMode) -> f64 {
+    let initial = InitialState {
+        speed: 60.0,
+        gear: Gear::Fourth,
+        hand_brake: HandBrake::Disengaged,
+        running: true,
+        ..Default::default()
+    };
+    let mut car = Car::warm_start(initial, CarConfig::default());
+    car.set_drive_mode(mode);
+    car.set_accelerator_position(1.0);
+    for _ in 0..30 {
+        car.update();
+    }
+    car.speed()
+}
+
+#[test]
+fn sport_outruns_normal_outruns_eco() {
+    let (eco, normal, sport) =
+        (flat_out(DriveMode::Eco), flat_out(DriveMode::Normal), flat_out(DriveMode::Sport));
+    assert!(eco < normal && normal < sport, "eco {eco}, normal {normal}, sport {sport}");
+}
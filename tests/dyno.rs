@@ -0,0 +1,14 @@
+use vehicle_dynamics::{dyno, CarConfig};
+
+#[test]
+fn climbing_takes_more_pedal_and_fuel() {
+    let flat = dyno(CarConfig::default(), 0.0, [60.0]);
+    let climb = dyno(CarConfig::default(), 0.1, [60.0]);
+    let (flat, climb) = (flat[0], climb[0]);
+
+    assert!(climb.accelerator > flat.accelerator, "{climb:?} against {flat:?}");
+    assert!(climb.road_load > 3.0 * flat.road_load, "{climb:?} against {flat:?}");
+    assert!(climb.litres_per_100km > 2.0 * flat.litres_per_100km, "{climb:?} against {flat:?}");
+    // A small car cruising on the flat
+    assert!((3.0..8.0).contains(&flat.litres_per_100km), "{flat:?}");
+}
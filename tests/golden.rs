@@ -0,0 +1,13 @@
+use std::path::Path;
+
+use vehicle_dynamics::Scenario;
+
+#[test]
+fn scenarios_match_golden_traces() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("golden");
+    for scenario in Scenario::ALL {
+        if let Err(e) = scenario.check(&dir, 1e-9) {
+            panic!("{}: {e}", scenario.name());
+        }
+    }
+}
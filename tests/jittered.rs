@@ -0,0 +1,19 @@
+#![cfg(feature = "rand")]
+
+use vehicle_dynamics::CarConfig;
+
+#[test]
+fn jitter_varies_efficiency_and_tire_wear_within_the_spread() {
+    let stock = CarConfig::default();
+    let cars: Vec<_> = (0..10).map(|seed| stock.jittered(0.05, seed)).collect();
+    for car in &cars {
+        assert!((car.bsfc / stock.bsfc - 1.0).abs() <= 0.05, "bsfc {}", car.bsfc);
+        assert!(
+            (car.tire_wear / stock.tire_wear - 1.0).abs() <= 0.05,
+            "tire wear {}",
+            car.tire_wear
+        );
+    }
+    assert!(cars.iter().any(|car| car.bsfc != cars[0].bsfc));
+    assert!(cars.iter().any(|car| car.tire_wear != cars[0].tire_wear));
+}
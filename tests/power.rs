@@ -0,0 +1,31 @@
+use vehicle_dynamics::{Car, CarConfig, PowerState};
+
+// Seconds of updates until the car reaches `state`
+fn seconds_until(car: &mut Car, state: PowerState, time_step: f64) -> f64 {
+    let mut elapsed = 0.0;
+    while car.power_state() != state {
+        assert!(elapsed < 120.0, "stuck in {:?}", car.power_state());
+        car.update();
+        elapsed += time_step;
+    }
+    elapsed
+}
+
+#[test]
+fn transitions_take_the_same_time_at_any_time_step() {
+    for time_step in [1.0, 0.1] {
+        let mut car = Car::with_config(0.5, CarConfig { time_step, ..CarConfig::default() });
+        car.set_brake_position(1.0);
+        car.turn_key(true).expect("engine starts");
+        let crank = seconds_until(&mut car, PowerState::Ready, time_step);
+        assert!((crank - 2.0).abs() < 1e-6, "cranked in {crank} s at a {time_step} s step");
+
+        car.turn_key(false).unwrap();
+        let sleep = seconds_until(&mut car, PowerState::Sleep, time_step);
+        assert!((sleep - 60.0).abs() < 1e-6, "slept after {sleep} s at a {time_step} s step");
+
+        car.wake();
+        let wake = seconds_until(&mut car, PowerState::Accessory, time_step);
+        assert!((wake - 1.0).abs() < 1e-6, "woke in {wake} s at a {time_step} s step");
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "rand")]
+
+use rand::{rngs::StdRng, SeedableRng};
+use vehicle_dynamics::{Car, RandomDriver};
+
+#[test]
+fn chances_out_of_range_are_clamped() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for chance in [-0.5, 1.5, f64::NAN] {
+        let mut driver = RandomDriver::default();
+        driver.brake_chance = chance;
+        driver.shift_chance = chance;
+        driver.hand_brake_chance = chance;
+        let mut car = Car::new(0.5);
+        for _ in 0..100 {
+            driver.drive(&mut car, &mut rng);
+            car.update();
+        }
+    }
+}
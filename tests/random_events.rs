@@ -0,0 +1,24 @@
+#![cfg(feature = "rand")]
+
+use rand::{rngs::StdRng, SeedableRng};
+use vehicle_dynamics::{Car, CarConfig, Gear, HandBrake, InitialState, RandomEvents};
+
+#[test]
+fn rates_out_of_range_never_panic() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for rate in [-1.0, 1e9, f64::NAN] {
+        let mut events = RandomEvents::new(rate, rate, rate).with_gusts(rate, 5.0);
+        let initial = InitialState {
+            speed: 50.0,
+            gear: Gear::Third,
+            hand_brake: HandBrake::Disengaged,
+            running: true,
+            ..Default::default()
+        };
+        let mut car = Car::warm_start(initial, CarConfig::default());
+        for _ in 0..100 {
+            events.apply(&mut car, &mut rng);
+            car.update();
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use vehicle_dynamics::{Car, CarConfig, Gear, HandBrake, InitialState};
+
+/// Speed after braking at 0.3 from 80 kmph in fourth for `updates` updates
+fn brake(config: CarConfig, updates: usize) -> f64 {
+    let initial = InitialState {
+        speed: 80.0,
+        gear: Gear::Fourth,
+        hand_brake: HandBrake::Disengaged,
+        running: true,
+        ..Default::default()
+    };
+    let mut car = Car::warm_start(initial, config);
+    car.set_brake_position(0.3);
+    for _ in 0..updates {
+        car.update();
+    }
+
+    car.speed()
+}
+
+#[test]
+fn sub_steps_match_short_updates() {
+    let short = brake(CarConfig { time_step: 0.1, ..CarConfig::default() }, 30);
+    let sub_stepped = brake(CarConfig { max_step: Some(0.1), ..CarConfig::default() }, 3);
+    assert!((short - sub_stepped).abs() < 1e-6, "{short} vs {sub_stepped}");
+}
+
+#[test]
+fn braking_barely_depends_on_the_step() {
+    let whole = brake(CarConfig::default(), 3);
+    let sub_stepped = brake(CarConfig { max_step: Some(0.1), ..CarConfig::default() }, 3);
+    assert!((whole - sub_stepped).abs() < 5.0, "{whole} vs {sub_stepped}");
+}
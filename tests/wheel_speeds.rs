@@ -0,0 +1,48 @@
+use vehicle_dynamics::{Car, CarConfig, Gear, HandBrake, InitialState, WheelSpeeds};
+
+fn moving(speed: f64, gear: Gear) -> Car {
+    let initial = InitialState {
+        speed,
+        gear,
+        hand_brake: HandBrake::Disengaged,
+        running: true,
+        ..Default::default()
+    };
+    Car::warm_start(initial, CarConfig::default())
+}
+
+fn all(wheels: WheelSpeeds) -> [f64; 4] {
+    [wheels.front_left, wheels.front_right, wheels.rear_left, wheels.rear_right]
+}
+
+#[test]
+fn braked_wheels_stop_with_the_car() {
+    let mut car = moving(100.0, Gear::Fourth);
+    car.set_brake_position(1.0);
+    while car.speed() > 0.0 {
+        car.update();
+        for wheel in all(car.wheel_speeds()) {
+            assert!((0.0..=car.speed()).contains(&wheel), "{wheel} at {}", car.speed());
+        }
+    }
+    assert_eq!(all(car.wheel_speeds()), [0.0; 4]);
+}
+
+#[test]
+fn driven_wheels_slip_a_bounded_amount() {
+    let mut car = moving(30.0, Gear::Second);
+    car.set_accelerator_position(1.0);
+    let mut slipped = false;
+    for _ in 0..10 {
+        car.update();
+        let wheels = car.wheel_speeds();
+        assert!(
+            (car.speed()..=car.speed() * 1.1).contains(&wheels.front_left),
+            "{wheels:?} at {}",
+            car.speed()
+        );
+        assert_eq!(wheels.rear_left, car.speed());
+        slipped |= wheels.front_left > car.speed();
+    }
+    assert!(slipped);
+}